@@ -0,0 +1,279 @@
+//! Deterministic stand-ins for the puzzle inputs, shaped like the real ones.
+//! Days with a generator in `aoc_2016::generate` use that one.
+
+use std::fmt::Write;
+
+use aoc_2016::generate::{self, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub fn day01(rng: &mut Rng) -> String {
+    generate::day01(rng.next_u64()).input
+}
+
+pub fn day02(rng: &mut Rng) -> String {
+    generate::day02(rng.next_u64()).input
+}
+
+pub fn day03(rng: &mut Rng) -> String {
+    generate::day03(rng.next_u64()).input
+}
+
+pub fn day04(rng: &mut Rng) -> String {
+    generate::day04(rng.next_u64()).input
+}
+
+pub fn day05(_rng: &mut Rng) -> String {
+    "abc".to_string()
+}
+
+pub fn day06(rng: &mut Rng) -> String {
+    generate::day06(rng.next_u64()).input
+}
+
+pub fn day07(rng: &mut Rng) -> String {
+    generate::day07(rng.next_u64()).input
+}
+
+pub fn day12(rng: &mut Rng) -> String {
+    format!(
+        "cpy 1 a
+cpy 1 b
+cpy {} d
+jnz c 2
+jnz 1 5
+cpy 7 c
+inc d
+dec c
+jnz c -2
+cpy a c
+inc a
+dec b
+jnz b -2
+cpy c b
+dec d
+jnz d -6
+cpy {} c
+cpy {} d
+inc a
+dec d
+jnz d -2
+dec c
+jnz c -5
+",
+        rng.range(24, 28),
+        rng.range(10, 20),
+        rng.range(10, 20),
+    )
+}
+
+pub fn day13(_rng: &mut Rng) -> String {
+    // Not every favourite number connects (1, 1) to (31, 39).
+    "1352".to_string()
+}
+
+pub fn day14(_rng: &mut Rng) -> String {
+    "abc".to_string()
+}
+
+pub fn day15(rng: &mut Rng) -> String {
+    // Part 2 adds a disc with 11 positions, so stay clear of it.
+    const PRIMES: [u64; 6] = [13, 17, 19, 23, 29, 31];
+
+    (1..=6)
+        .map(|number| {
+            let positions = PRIMES[number - 1];
+            format!(
+                "Disc #{number} has {positions} positions; at time=0, it is at position {}.\n",
+                rng.below(positions)
+            )
+        })
+        .collect()
+}
+
+pub fn day16(rng: &mut Rng) -> String {
+    rng.letters(b"01", 17)
+}
+
+pub fn day17(_rng: &mut Rng) -> String {
+    // Most passcodes lock every door early; this one has long paths.
+    "ulqzkmiv".to_string()
+}
+
+pub fn day18(rng: &mut Rng) -> String {
+    rng.letters(b"^..", 100)
+}
+
+pub fn day19(rng: &mut Rng) -> String {
+    rng.range(3_000_000, 3_100_000).to_string()
+}
+
+pub fn day20(rng: &mut Rng) -> String {
+    // Short ranges cannot cover all of u32, so there is always an allowed IP.
+    (0..1000)
+        .map(|_| {
+            let start = rng.below(u32::MAX as u64 - (1 << 22));
+            format!("{start}-{}\n", start + rng.below(1 << 22))
+        })
+        .collect()
+}
+
+pub fn day21(rng: &mut Rng) -> String {
+    let letters = &LETTERS[..8];
+
+    (0..100)
+        .map(|_| {
+            let (x, y) = (rng.below(8), rng.below(8));
+            let (a, b) = (rng.pick(letters) as char, rng.pick(letters) as char);
+            match rng.below(7) {
+                0 => format!("swap position {x} with position {y}\n"),
+                1 => format!("swap letter {a} with letter {b}\n"),
+                2 => format!("rotate left {x} steps\n"),
+                3 => format!("rotate right {x} steps\n"),
+                4 => format!("rotate based on position of letter {a}\n"),
+                5 => format!("reverse positions {} through {}\n", x.min(y), x.max(y)),
+                _ => format!("move position {x} to position {y}\n"),
+            }
+        })
+        .collect()
+}
+
+pub fn day22(rng: &mut Rng) -> String {
+    let (width, height) = (33, 30);
+    let empty = (rng.range(10, 30), rng.range(20, 30));
+    let wall_row = rng.range(5, 15);
+
+    let mut out = "root@ebhq-gridcenter# df -h\nFilesystem              Size  Used  Avail  Use%\n"
+        .to_string();
+    for x in 0..width {
+        for y in 0..height {
+            let (size, used) = if (x, y) == empty {
+                (rng.range(85, 95), 0)
+            } else if y == wall_row && x > 3 {
+                (rng.range(500, 510), rng.range(490, 500))
+            } else {
+                (rng.range(85, 95), rng.range(64, 74))
+            };
+
+            writeln!(
+                out,
+                "/dev/grid/node-x{x}-y{y}   {size}T   {used}T   {}T   {}%",
+                size - used,
+                used * 100 / size
+            )
+            .expect("Write to string failed");
+        }
+    }
+
+    out
+}
+
+pub fn day23(rng: &mut Rng) -> String {
+    format!(
+        "cpy a b
+dec b
+cpy a d
+cpy 0 a
+cpy b c
+inc a
+dec c
+jnz c -2
+dec d
+jnz d -5
+dec b
+cpy b c
+cpy c d
+dec d
+inc c
+jnz d -2
+tgl c
+cpy -16 c
+jnz 1 c
+cpy {} c
+jnz {} d
+inc a
+inc d
+jnz d -2
+inc c
+jnz c -5
+",
+        rng.range(70, 100),
+        rng.range(70, 100),
+    )
+}
+
+pub fn day24(rng: &mut Rng) -> String {
+    let (width, height) = (179, 37);
+
+    // Odd rows are open corridors and column 1 links them, so every open cell
+    // is reachable.
+    let mut grid: Vec<Vec<u8>> = (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let border = row == 0 || row == height - 1 || col == 0 || col == width - 1;
+                    if border {
+                        b'#'
+                    } else if row % 2 == 1 || col == 1 || rng.below(3) > 0 {
+                        b'.'
+                    } else {
+                        b'#'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    for point in b'0'..=b'7' {
+        loop {
+            let row = rng.range(0, (height / 2) as u64) as usize * 2 + 1;
+            let col = rng.range(1, width as u64 - 1) as usize;
+            if grid[row][col] == b'.' {
+                grid[row][col] = point;
+                break;
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).expect("Map is ASCII") + "\n")
+        .collect()
+}
+
+pub fn day25(rng: &mut Rng) -> String {
+    format!(
+        "cpy a d
+cpy {} c
+cpy {} b
+inc d
+dec b
+jnz b -2
+dec c
+jnz c -5
+cpy d a
+jnz 0 0
+cpy a b
+cpy 0 a
+cpy 2 c
+jnz b 2
+jnz 1 6
+dec b
+dec c
+jnz c -4
+inc a
+jnz 1 -7
+cpy 2 b
+jnz c 2
+jnz 1 4
+dec b
+dec c
+jnz 1 -4
+jnz 0 0
+out b
+jnz a -19
+jnz 1 -21
+",
+        rng.range(4, 10),
+        rng.range(150, 300),
+    )
+}
@@ -0,0 +1,88 @@
+use std::{hint::black_box, time::Duration};
+
+use aoc_2016::{generate::Rng, solution};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+mod inputs;
+
+struct Day {
+    day: u32,
+    input: fn(&mut Rng) -> String,
+    // Days without an `#[aoc_generator]` work on the raw input.
+    generator: bool,
+    // Brute force days cannot afford Criterion's default 100 samples.
+    slow: bool,
+}
+
+const fn day(day: u32, input: fn(&mut Rng) -> String, generator: bool, slow: bool) -> Day {
+    Day {
+        day,
+        input,
+        generator,
+        slow,
+    }
+}
+
+const DAYS: &[Day] = &[
+    day(1, inputs::day01, true, false),
+    day(2, inputs::day02, true, false),
+    day(3, inputs::day03, true, false),
+    day(4, inputs::day04, true, false),
+    day(5, inputs::day05, false, true),
+    day(6, inputs::day06, true, false),
+    day(7, inputs::day07, true, false),
+    day(12, inputs::day12, true, false),
+    day(13, inputs::day13, true, false),
+    day(14, inputs::day14, false, true),
+    day(15, inputs::day15, true, false),
+    day(16, inputs::day16, true, true),
+    day(17, inputs::day17, false, true),
+    day(18, inputs::day18, true, true),
+    day(19, inputs::day19, true, true),
+    day(20, inputs::day20, true, false),
+    day(21, inputs::day21, true, false),
+    day(22, inputs::day22, true, false),
+    day(23, inputs::day23, true, false),
+    day(24, inputs::day24, true, false),
+    day(25, inputs::day25, true, true),
+];
+
+fn bench_day(c: &mut Criterion, day: &Day) {
+    let entry = solution::find(day.day).expect("Every benched day is registered");
+    let input = (day.input)(&mut Rng::new(2016 * 100 + day.day as u64));
+
+    let mut group = c.benchmark_group(format!("day{:02}", day.day));
+    if day.slow {
+        group.sample_size(10);
+        group.warm_up_time(Duration::from_millis(500));
+    }
+
+    if day.generator {
+        group.bench_function("generator", |b| {
+            b.iter(|| {
+                entry
+                    .parse(black_box(&input))
+                    .expect("Synthetic input parses")
+            })
+        });
+    }
+
+    let parsed = entry.parse(&input).expect("Synthetic input parses");
+    for part in entry.parts() {
+        parsed.solve(part).expect("Synthetic input solves");
+        group.bench_function(format!("part{part}"), |b| {
+            b.iter(|| parsed.solve(black_box(part)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_all(c: &mut Criterion) {
+    for day in DAYS {
+        bench_day(c, day);
+    }
+}
+
+criterion_group!(benches, bench_all);
+criterion_main!(benches);
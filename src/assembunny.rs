@@ -0,0 +1,791 @@
+use std::{collections::BTreeMap, error, fmt, str::FromStr};
+
+use itertools::Itertools;
+
+pub const REGISTERS: usize = 4;
+
+pub type Registers = [i64; REGISTERS];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Register(usize),
+    Value(i64),
+}
+
+impl FromStr for Operand {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "a" => Self::Register(0),
+            "b" => Self::Register(1),
+            "c" => Self::Register(2),
+            "d" => Self::Register(3),
+            _ => Self::Value(s.parse()?),
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Register(r) => write!(f, "{}", (b'a' + *r as u8) as char),
+            Self::Value(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+// Every instruction takes generic operands because `tgl` can turn valid code
+// into nonsense such as `cpy 1 2`, which is then skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Cpy(Operand, Operand),
+    Inc(Operand),
+    Dec(Operand),
+    Jnz(Operand, Operand),
+    Tgl(Operand),
+    Out(Operand),
+}
+
+impl Instruction {
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Inc(x) => Self::Dec(x),
+            Self::Dec(x) | Self::Tgl(x) | Self::Out(x) => Self::Inc(x),
+            Self::Jnz(x, y) => Self::Cpy(x, y),
+            Self::Cpy(x, y) => Self::Jnz(x, y),
+        }
+    }
+
+    fn writes(self) -> Option<usize> {
+        match self {
+            Self::Cpy(_, Operand::Register(r))
+            | Self::Inc(Operand::Register(r))
+            | Self::Dec(Operand::Register(r)) => Some(r),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect_vec();
+
+        Ok(match words.as_slice() {
+            ["cpy", x, y] => Self::Cpy(x.parse()?, y.parse()?),
+            ["inc", x] => Self::Inc(x.parse()?),
+            ["dec", x] => Self::Dec(x.parse()?),
+            ["jnz", x, y] => Self::Jnz(x.parse()?, y.parse()?),
+            ["tgl", x] => Self::Tgl(x.parse()?),
+            ["out", x] => Self::Out(x.parse()?),
+            _ => return Err(format!("Unknown instruction {s:?}").into()),
+        })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::Cpy(x, y) => format!("cpy {x} {y}"),
+            Self::Inc(x) => format!("inc {x}"),
+            Self::Dec(x) => format!("dec {x}"),
+            Self::Jnz(x, y) => format!("jnz {x} {y}"),
+            Self::Tgl(x) => format!("tgl {x}"),
+            Self::Out(x) => format!("out {x}"),
+        };
+
+        // Let width and alignment apply to the whole instruction.
+        f.pad(&text)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+    pub source: Box<dyn error::Error>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {} ({:?}): {}", self.line, self.text, self.source)
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLine {
+    pub number: usize,
+    pub text: String,
+}
+
+/// Parsed program that remembers which source line each instruction came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+    pub lines: Vec<SourceLine>,
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut program = Self::default();
+
+        for (i, text) in s.lines().enumerate() {
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+
+            let instruction = text.parse().map_err(|source| ParseError {
+                line: i + 1,
+                text: text.to_string(),
+                source,
+            })?;
+
+            program.instructions.push(instruction);
+            program.lines.push(SourceLine {
+                number: i + 1,
+                text: text.to_string(),
+            });
+        }
+
+        Ok(program)
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for instr in &self.instructions {
+            writeln!(f, "{instr}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+    /// Register tested by the closing `jnz` if the body changes it.
+    pub counter: Option<usize>,
+}
+
+impl Program {
+    fn jump_target(&self, pc: usize) -> Option<usize> {
+        match self.instructions[pc] {
+            Instruction::Jnz(_, Operand::Value(offset)) => pc.checked_add_signed(offset as isize),
+            _ => None,
+        }
+    }
+
+    /// Every instruction reached by a constant `jnz`, with its label number.
+    pub fn jump_targets(&self) -> BTreeMap<usize, usize> {
+        (0..self.instructions.len())
+            .filter_map(|pc| self.jump_target(pc))
+            .filter(|&target| target < self.instructions.len())
+            .sorted()
+            .dedup()
+            .enumerate()
+            .map(|(label, target)| (target, label))
+            .collect()
+    }
+
+    /// Backward constant jumps, ordered by their closing `jnz`.
+    pub fn loops(&self) -> Vec<Loop> {
+        (0..self.instructions.len())
+            .filter_map(|end| {
+                let start = self.jump_target(end).filter(|&start| start <= end)?;
+                let Instruction::Jnz(condition, _) = self.instructions[end] else {
+                    unreachable!("Only jnz has a jump target")
+                };
+
+                let counter = match condition {
+                    Operand::Register(r)
+                        if self.instructions[start..end]
+                            .iter()
+                            .any(|i| i.writes() == Some(r)) =>
+                    {
+                        Some(r)
+                    }
+                    _ => None,
+                };
+
+                Some(Loop {
+                    start,
+                    end,
+                    counter,
+                })
+            })
+            .collect()
+    }
+
+    /// Listing with labels on jump targets and comments on jumps, loops and
+    /// the arithmetic shortcuts the [`Machine`] would take.
+    pub fn disassemble(&self) -> String {
+        let labels = self.jump_targets();
+        let loops = self.loops();
+        let shortcuts = find_shortcuts(&self.instructions);
+        let mut out = String::new();
+
+        for (pc, instr) in self.instructions.iter().enumerate() {
+            let label = labels
+                .get(&pc)
+                .map(|l| format!("L{l}:"))
+                .unwrap_or_default();
+
+            let mut comments = vec![];
+            if let Instruction::Jnz(_, Operand::Value(_)) = instr {
+                match self.jump_target(pc).and_then(|t| labels.get(&t)) {
+                    Some(l) => comments.push(format!("-> L{l}")),
+                    None => comments.push("-> exit".to_string()),
+                }
+            }
+            if let Some(lp) = loops.iter().find(|lp| lp.end == pc) {
+                comments.push(match lp.counter {
+                    Some(r) => format!("loop while {} != 0", Operand::Register(r)),
+                    None => "loop".to_string(),
+                });
+            }
+            if let Some(shortcut) = shortcuts[pc] {
+                comments.push(shortcut.to_string());
+            }
+
+            let line = if comments.is_empty() {
+                format!("{pc:4}  {label:5}{instr}")
+            } else {
+                format!("{pc:4}  {label:5}{instr:12}; {}", comments.join(", "))
+            };
+            out.push_str(&line);
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+// Loops that only add or multiply, replaced by their result when they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shortcut {
+    // inc target / dec counter / jnz counter -2
+    Add {
+        target: usize,
+        counter: usize,
+    },
+    // cpy factor scratch / <Add into target with scratch> / dec counter / jnz counter -5
+    Multiply {
+        target: usize,
+        factor: Operand,
+        scratch: usize,
+        counter: usize,
+    },
+}
+
+impl Shortcut {
+    fn len(self) -> usize {
+        match self {
+            Self::Add { .. } => 3,
+            Self::Multiply { .. } => 6,
+        }
+    }
+
+    fn detect(code: &[Instruction]) -> Option<Self> {
+        use Instruction::*;
+        use Operand::*;
+
+        match *code {
+            [
+                Cpy(factor, Register(scratch)),
+                ref add @ ..,
+                Dec(Register(counter)),
+                Jnz(Register(jump), Value(-5)),
+            ] if add.len() == 3 && jump == counter => {
+                let Some(Self::Add { target, counter: c }) = Self::detect(add) else {
+                    return None;
+                };
+
+                let distinct = [target, scratch, counter].iter().all_unique();
+                let factor_ok = factor != Register(target)
+                    && factor != Register(counter)
+                    && factor != Register(scratch);
+                (c == scratch && distinct && factor_ok).then_some(Self::Multiply {
+                    target,
+                    factor,
+                    scratch,
+                    counter,
+                })
+            }
+            [
+                Inc(Register(target)),
+                Dec(Register(counter)),
+                Jnz(Register(jump), Value(-2)),
+            ]
+            | [
+                Dec(Register(counter)),
+                Inc(Register(target)),
+                Jnz(Register(jump), Value(-2)),
+            ] if jump == counter && target != counter => Some(Self::Add { target, counter }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reg = Operand::Register;
+        match *self {
+            Self::Add { target, counter } => {
+                write!(f, "{} += {}", reg(target), reg(counter))
+            }
+            Self::Multiply {
+                target,
+                factor,
+                counter,
+                ..
+            } => write!(f, "{} += {factor} * {}", reg(target), reg(counter)),
+        }
+    }
+}
+
+fn find_shortcuts(program: &[Instruction]) -> Vec<Option<Shortcut>> {
+    (0..program.len())
+        .map(|pc| {
+            [6, 3]
+                .into_iter()
+                .filter_map(|len| program.get(pc..pc + len))
+                .find_map(Shortcut::detect)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceEntry {
+    pub pc: usize,
+    pub instruction: Instruction,
+    pub registers: Registers,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.registers;
+        write!(
+            f,
+            "{:4}  {:12}a={a} b={b} c={c} d={d}",
+            self.pc, self.instruction
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    program: Vec<Instruction>,
+    registers: Registers,
+    pc: usize,
+    shortcuts: Option<Vec<Option<Shortcut>>>,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl Machine {
+    pub fn new(program: &[Instruction], optimise: bool) -> Self {
+        let mut machine = Self {
+            program: program.to_vec(),
+            registers: [0; REGISTERS],
+            pc: 0,
+            shortcuts: optimise.then(Vec::new),
+            trace: None,
+        };
+
+        machine.refresh_shortcuts();
+        machine
+    }
+
+    /// Records the registers after every instruction from now on.
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    pub fn register(&self, r: usize) -> i64 {
+        self.registers[r]
+    }
+
+    pub fn set_register(&mut self, r: usize, value: i64) {
+        self.registers[r] = value;
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    fn refresh_shortcuts(&mut self) {
+        if let Some(shortcuts) = &mut self.shortcuts {
+            *shortcuts = find_shortcuts(&self.program);
+        }
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Value(v) => v,
+        }
+    }
+
+    fn jump(&mut self, offset: i64) {
+        self.pc = self.pc.wrapping_add_signed(offset as isize);
+    }
+
+    // Only take a shortcut when the loop would actually count down to zero.
+    fn try_shortcut(&mut self) -> bool {
+        let Some(shortcut) = self
+            .shortcuts
+            .as_ref()
+            .and_then(|s| s.get(self.pc).copied().flatten())
+        else {
+            return false;
+        };
+
+        match shortcut {
+            Shortcut::Add { target, counter } => {
+                let times = self.registers[counter];
+                if times <= 0 {
+                    return false;
+                }
+
+                self.registers[target] += times;
+                self.registers[counter] = 0;
+            }
+            Shortcut::Multiply {
+                target,
+                factor,
+                scratch,
+                counter,
+            } => {
+                let (factor, times) = (self.value(factor), self.registers[counter]);
+                if factor <= 0 || times <= 0 {
+                    return false;
+                }
+
+                self.registers[target] += factor * times;
+                self.registers[scratch] = 0;
+                self.registers[counter] = 0;
+            }
+        }
+
+        self.pc += shortcut.len();
+        true
+    }
+
+    fn execute(&mut self) -> Option<i64> {
+        if self.try_shortcut() {
+            return None;
+        }
+
+        match self.program[self.pc] {
+            Instruction::Cpy(x, Operand::Register(r)) => self.registers[r] = self.value(x),
+            Instruction::Inc(Operand::Register(r)) => self.registers[r] += 1,
+            Instruction::Dec(Operand::Register(r)) => self.registers[r] -= 1,
+            Instruction::Jnz(x, y) if self.value(x) != 0 => {
+                self.jump(self.value(y));
+                return None;
+            }
+            Instruction::Tgl(x) => {
+                let target = self.pc.wrapping_add_signed(self.value(x) as isize);
+                if let Some(instr) = self.program.get_mut(target) {
+                    *instr = instr.toggled();
+                    self.refresh_shortcuts();
+                }
+            }
+            Instruction::Out(x) => {
+                self.pc += 1;
+                return Some(self.value(x));
+            }
+            // Invalid after a toggle, or a jnz that is not taken.
+            _ => (),
+        }
+
+        self.pc += 1;
+        None
+    }
+
+    /// Executes one instruction (or one shortcut) and returns what it output.
+    pub fn step(&mut self) -> Option<i64> {
+        let (pc, instruction) = (self.pc, self.program[self.pc]);
+        let output = self.execute();
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                pc,
+                instruction,
+                registers: self.registers,
+            });
+        }
+
+        output
+    }
+
+    pub fn run(&mut self) {
+        while !self.is_halted() {
+            self.step();
+        }
+    }
+}
+
+// Computes a! + 6 * 7 for a >= 6 the same way the day 23 puzzle inputs
+// do, toggling the tail of the program on its way.
+#[cfg(test)]
+pub(crate) const FACTORIAL: &str = "
+    cpy a b
+    dec b
+    cpy a d
+    cpy 0 a
+    cpy b c
+    inc a
+    dec c
+    jnz c -2
+    dec d
+    jnz d -5
+    dec b
+    cpy b c
+    cpy c d
+    dec d
+    inc c
+    jnz d -2
+    tgl c
+    cpy -16 c
+    jnz 1 c
+    cpy 6 c
+    jnz 7 d
+    inc a
+    inc d
+    jnz d -2
+    inc c
+    jnz c -5
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &Program, a: i64, optimise: bool) -> i64 {
+        let mut machine = Machine::new(&program.instructions, optimise);
+        machine.set_register(0, a);
+        machine.run();
+        machine.register(0)
+    }
+
+    #[test]
+    fn parse_keeps_source_lines() {
+        let program: Program = "\n  cpy 41 a\n\ninc   a\n".parse().unwrap();
+        assert_eq!(
+            program.lines,
+            vec![
+                SourceLine {
+                    number: 2,
+                    text: "cpy 41 a".to_string()
+                },
+                SourceLine {
+                    number: 4,
+                    text: "inc   a".to_string()
+                },
+            ]
+        );
+
+        let err = "inc a\njmp 2".parse::<Program>().unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "jmp 2");
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let program: Program = FACTORIAL.parse().unwrap();
+        let formatted = program.to_string();
+        let reparsed: Program = formatted.parse().unwrap();
+
+        assert_eq!(reparsed.instructions, program.instructions);
+        assert_eq!(reparsed.to_string(), formatted);
+        assert!(formatted.starts_with("cpy a b\ndec b\n"));
+    }
+
+    #[test]
+    fn disassemble_small_program() {
+        let program: Program = "
+            cpy 2 a
+            cpy 3 b
+            inc a
+            dec b
+            jnz b -2
+            jnz a 2
+            out a
+            "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            program.disassemble(),
+            "   0       cpy 2 a
+   1       cpy 3 b
+   2  L0:  inc a       ; a += b
+   3       dec b
+   4       jnz b -2    ; -> L0, loop while b != 0
+   5       jnz a 2     ; -> exit
+   6       out a
+"
+        );
+    }
+
+    #[test]
+    fn detects_loops() {
+        let program: Program = FACTORIAL.parse().unwrap();
+        let loops = program.loops();
+
+        assert!(loops.contains(&Loop {
+            start: 5,
+            end: 7,
+            counter: Some(2)
+        }));
+        assert!(loops.contains(&Loop {
+            start: 4,
+            end: 9,
+            counter: Some(3)
+        }));
+    }
+
+    #[test]
+    fn detects_shortcuts() {
+        let program: Program = FACTORIAL.parse().unwrap();
+        let shortcuts = find_shortcuts(&program.instructions);
+
+        assert_eq!(
+            shortcuts[4],
+            Some(Shortcut::Multiply {
+                target: 0,
+                factor: Operand::Register(1),
+                scratch: 2,
+                counter: 3
+            })
+        );
+        assert_eq!(
+            shortcuts[13],
+            Some(Shortcut::Add {
+                target: 2,
+                counter: 3
+            })
+        );
+    }
+
+    #[test]
+    fn self_copied_factor_is_not_a_multiply() {
+        // `cpy c c` does not reload `c`, so the second pass starts from zero,
+        // counts down past it and never ends; it is not `a += c * d`.
+        let program: Program = "
+            cpy c c
+            inc a
+            dec c
+            jnz c -2
+            dec d
+            jnz d -5
+            "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            find_shortcuts(&program.instructions)[0],
+            None,
+            "cpy c c must not start a multiply"
+        );
+    }
+
+    #[test]
+    fn shortcuts_match_plain_run() {
+        let program: Program = FACTORIAL.parse().unwrap();
+        for a in 6..=8 {
+            let factorial: i64 = (1..=a).product();
+            assert_eq!(run(&program, a, false), factorial + 42, "a = {a}");
+            assert_eq!(run(&program, a, true), factorial + 42, "a = {a}");
+        }
+    }
+
+    #[test]
+    fn toggled_shortcut_falls_back() {
+        // The `tgl` turns the multiply loop's `cpy b c` into `jnz b c`, so it
+        // must no longer be treated as a multiplication.
+        let program: Program = "
+            cpy 3 b
+            cpy 2 d
+            tgl d
+            cpy 0 a
+            cpy b c
+            inc a
+            dec c
+            jnz c -2
+            dec d
+            jnz d -5
+            "
+        .parse()
+        .unwrap();
+
+        let mut machine = Machine::new(&program.instructions, true);
+        assert!(machine.shortcuts.as_ref().unwrap()[4].is_some());
+        for _ in 0..3 {
+            machine.step();
+        }
+
+        assert_eq!(
+            machine.program()[4],
+            Instruction::Jnz(Operand::Register(1), Operand::Register(2))
+        );
+        assert_eq!(machine.shortcuts.as_ref().unwrap()[4], None);
+    }
+
+    #[test]
+    fn trace_logs_every_step() {
+        let program: Program = "cpy 2 a\ndec a\njnz a -1\nout a".parse().unwrap();
+        let mut machine = Machine::new(&program.instructions, false);
+        machine.enable_trace();
+        machine.run();
+
+        let trace = machine.trace().iter().map(|e| e.to_string()).collect_vec();
+        assert_eq!(
+            trace,
+            vec![
+                "   0  cpy 2 a     a=2 b=0 c=0 d=0",
+                "   1  dec a       a=1 b=0 c=0 d=0",
+                "   2  jnz a -1    a=1 b=0 c=0 d=0",
+                "   1  dec a       a=0 b=0 c=0 d=0",
+                "   2  jnz a -1    a=0 b=0 c=0 d=0",
+                "   3  out a       a=0 b=0 c=0 d=0",
+            ]
+        );
+    }
+
+    #[test]
+    #[ignore = "Slow without the multiply shortcut"]
+    fn large_factorial_without_shortcuts() {
+        let program: Program = FACTORIAL.parse().unwrap();
+        assert_eq!(run(&program, 10, false), run(&program, 10, true));
+    }
+}
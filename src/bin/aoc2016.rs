@@ -0,0 +1,168 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use aoc_2016::{
+    report::{self, Record},
+    runner::RunError,
+    solution,
+};
+
+const USAGE: &str =
+    "Usage: aoc2016 --day <DAY> [--part <PART>] [--input <PATH>] [--format <FORMAT>]
+       aoc2016 --list
+
+Runs the solvers for one day on the given input file, or on stdin if no path
+(or `-`) is given. Without --part every part of the day is run. Answers go to
+stdout and timings to stderr. --list prints every implemented day.
+
+With --format json or csv, one record per part goes to stdout instead, with the
+answer, the parse and solve times in nanoseconds, and any error.";
+
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+struct Args {
+    day: u32,
+    part: Option<u32>,
+    input: Option<String>,
+    format: Format,
+}
+
+enum Command {
+    Help,
+    List,
+    Run(Args),
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let (mut day, mut part, mut input, mut format) = (None, None, None, None);
+
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {flag}"));
+        match flag.as_str() {
+            "-d" | "--day" => day = Some(value()?),
+            "-p" | "--part" => part = Some(value()?),
+            "-i" | "--input" => input = Some(value()?),
+            "-f" | "--format" => format = Some(value()?),
+            "-h" | "--help" => return Ok(Command::Help),
+            "-l" | "--list" => return Ok(Command::List),
+            _ => return Err(format!("Unexpected argument {flag:?}")),
+        }
+    }
+
+    let number = |name: &str, value: String| {
+        value
+            .parse()
+            .map_err(|_| format!("Invalid {name} {value:?}"))
+    };
+
+    Ok(Command::Run(Args {
+        day: number("day", day.ok_or("--day is required")?)?,
+        part: part.map(|part| number("part", part)).transpose()?,
+        input: input.filter(|path| path != "-"),
+        format: match format.as_deref() {
+            None | Some("text") => Format::Text,
+            Some("json") => Format::Json,
+            Some("csv") => Format::Csv,
+            Some(other) => return Err(format!("Invalid format {other:?}")),
+        },
+    }))
+}
+
+fn read_input(path: Option<&str>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::List) => {
+            for entry in solution::REGISTRY {
+                println!("Day {:2}: {}", entry.day, entry.title);
+            }
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let Some(entry) = solution::find(args.day) else {
+        eprintln!("No solver for day {}", args.day);
+        return ExitCode::from(2);
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => entry.parts().collect(),
+    };
+    if let Some(&part) = parts
+        .iter()
+        .find(|&&part| !entry.parts().any(|p| p == part))
+    {
+        eprintln!(
+            "{}",
+            RunError::UnknownPuzzle {
+                day: args.day,
+                part
+            }
+        );
+        return ExitCode::from(2);
+    }
+
+    let input = match read_input(args.input.as_deref()) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    let mut records = vec![];
+    for (part, result) in parts.iter().zip(entry.run_parts(&parts, &input)) {
+        if result.is_err() {
+            status = ExitCode::FAILURE;
+        }
+
+        match (&args.format, result) {
+            (Format::Text, Ok(run)) => {
+                println!("Day {} - Part {part}: {}", args.day, run.answer);
+                eprintln!("\tparse: {:?}, solve: {:?}", run.parse, run.solve);
+            }
+            (Format::Text, Err(e)) => eprintln!("Day {} - Part {part}: {e}", args.day),
+            (_, result) => records.push(Record::new(args.day, *part, result)),
+        }
+    }
+
+    let written = match args.format {
+        Format::Text => Ok(()),
+        Format::Json => report::write_json(&records, io::stdout())
+            .map_err(|e| e.to_string())
+            .map(|()| println!()),
+        Format::Csv => report::write_csv(&records, io::stdout()).map_err(|e| e.to_string()),
+    };
+    if let Err(e) = written {
+        eprintln!("Failed to write report: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    status
+}
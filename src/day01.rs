@@ -110,8 +110,20 @@ fn part2(input: &[(Turn, isize)]) -> usize {
     unreachable!("Infinite loop");
 }
 
+solution! {
+    Day01 {
+        day: 1,
+        title: "No Time for a Taxicab",
+        input: Vec<(Turn, isize)> = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -125,4 +137,26 @@ mod tests {
     fn part2_example() {
         assert_eq!(part2(&parse("R8, R4, R4, R8")), 4);
     }
+
+    fn turn() -> impl Strategy<Value = (Turn, isize)> {
+        (
+            prop_oneof![Just(Turn::Left), Just(Turn::Right)],
+            0..100isize,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn part1_ignores_mirroring(input in prop::collection::vec(turn(), 1..50)) {
+            let mirrored: Vec<_> = input
+                .iter()
+                .map(|&(turn, distance)| match turn {
+                    Turn::Left => (Turn::Right, distance),
+                    Turn::Right => (Turn::Left, distance),
+                })
+                .collect();
+
+            prop_assert_eq!(part1(&input), part1(&mirrored));
+        }
+    }
 }
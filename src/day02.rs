@@ -63,7 +63,7 @@ impl KeyPad {
                 }
             }
             Instruction::Right => {
-                if self.0 % 3 != 0 {
+                if !self.0.is_multiple_of(3) {
                     self.0 += 1;
                 }
             }
@@ -136,8 +136,20 @@ fn part2(input: &[Vec<Instruction>]) -> String {
     out
 }
 
+solution! {
+    Day02 {
+        day: 2,
+        title: "Bathroom Security",
+        input: Vec<Vec<Instruction>> = parse,
+        part1: String = part1,
+        part2: String = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const EXAMPLE1: &str = "
@@ -156,4 +168,33 @@ mod tests {
     fn part2_example() {
         assert_eq!(part2(&parse(EXAMPLE1)), "5DB3");
     }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            Just(Instruction::Up),
+            Just(Instruction::Down),
+            Just(Instruction::Left),
+            Just(Instruction::Right),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn part1_stays_on_keypad(instrs in prop::collection::vec(instruction(), 0..100)) {
+            let mut keypad = KeyPad::default();
+            for instr in instrs {
+                keypad.move_part1(instr);
+                prop_assert!(b"123456789".contains(&keypad.0), "{:?}", keypad);
+            }
+        }
+
+        #[test]
+        fn part2_stays_on_keypad(instrs in prop::collection::vec(instruction(), 0..100)) {
+            let mut keypad = KeyPad::default();
+            for instr in instrs {
+                keypad.move_part2(instr);
+                prop_assert!(b"123456789ABCD".contains(&keypad.0), "{:?}", keypad);
+            }
+        }
+    }
 }
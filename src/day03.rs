@@ -62,8 +62,20 @@ fn part2(input: &[Triangle]) -> usize {
         .count()
 }
 
+solution! {
+    Day03 {
+        day: 3,
+        title: "Squares With Three Sides",
+        input: Vec<Triangle> = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -71,4 +83,14 @@ mod tests {
         assert!(!Triangle(5, 10, 25).is_valid());
         assert!(Triangle(5, 10, 7).is_valid());
     }
+
+    proptest! {
+        #[test]
+        fn validity_ignores_side_order(a in 0..10_000, b in 0..10_000, c in 0..10_000) {
+            let valid = Triangle(a, b, c).is_valid();
+            for (x, y, z) in [(a, c, b), (b, a, c), (b, c, a), (c, a, b), (c, b, a)] {
+                prop_assert_eq!(Triangle(x, y, z).is_valid(), valid);
+            }
+        }
+    }
 }
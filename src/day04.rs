@@ -1,14 +1,9 @@
-use std::{
-    cmp::Reverse,
-    error,
-    fmt::{self, Write},
-    str::FromStr,
-};
+use std::{cmp::Reverse, error, fmt, str::FromStr};
 
 use itertools::Itertools;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Room {
+pub struct Room {
     name: Vec<String>,
     sector_id: u16,
     checksum: [u8; 5],
@@ -136,23 +131,71 @@ fn part1(input: &[Room]) -> u64 {
         .sum()
 }
 
-#[aoc(day04, part2)]
-fn part2(input: &[Room]) -> String {
-    let mut buf = String::new();
+/// A room whose checksum holds up, with its name decrypted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RealRoom {
+    pub sector_id: u16,
+    pub words: Vec<String>,
+    pub room: Room,
+}
+
+impl RealRoom {
+    /// The decrypted words joined by spaces, e.g. "northpole object storage".
+    pub fn name(&self) -> String {
+        self.words.join(" ")
+    }
+}
 
-    for (room, decrypted) in input
+pub fn real_rooms(input: &[Room]) -> Vec<RealRoom> {
+    input
         .iter()
         .filter(|room| room.is_real_room())
-        .map(|room| (room, room.decrypt().iter().join(" ")))
-    {
-        writeln!(buf, "{:5}: {}", room.sector_id, decrypted).expect("Write to string failed");
+        .map(|room| RealRoom {
+            sector_id: room.sector_id,
+            words: room.decrypt(),
+            room: room.clone(),
+        })
+        .collect()
+}
+
+pub fn rooms_where(
+    rooms: &[RealRoom],
+    predicate: impl Fn(&RealRoom) -> bool,
+) -> impl Iterator<Item = &RealRoom> {
+    rooms.iter().filter(move |room| predicate(room))
+}
+
+/// Rooms whose decrypted name contains `needle`.
+pub fn rooms_named<'a>(rooms: &'a [RealRoom], needle: &str) -> impl Iterator<Item = &'a RealRoom> {
+    rooms_where(rooms, move |room| room.name().contains(needle))
+}
+
+#[aoc(day04, part2)]
+fn part2(input: &[Room]) -> Result<u16, String> {
+    let rooms = real_rooms(input);
+    let mut north_pole = rooms_named(&rooms, "northpole");
+
+    match (north_pole.next(), north_pole.next()) {
+        (Some(room), None) => Ok(room.sector_id),
+        (None, _) => Err("No room stores North Pole objects".to_string()),
+        (Some(_), Some(_)) => Err("Several rooms mention the North Pole".to_string()),
     }
+}
 
-    buf
+solution! {
+    Day04 {
+        day: 4,
+        title: "Security Through Obscurity",
+        input: Vec<Room> = parse,
+        part1: u64 = part1,
+        part2: u16 = part2,
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const EXAMPLE1: &str = "aaaaa-bbb-z-y-x-123[abxyz]";
@@ -189,4 +232,68 @@ mod tests {
             vec!["very", "encrypted", "name"]
         );
     }
+
+    #[test]
+    fn part2_rooms() {
+        let input = parse(
+            "qzmt-zixmtkozy-ivhz-343[zimth]
+            jknpdlkha-kxfayp-opknwca-394[kapnc]
+            jknpdlkha-kxfayp-opknwca-394[decoy]
+            qzmt-zixmtkozy-ivhz-343[abcde]",
+        )
+        .unwrap();
+        let rooms = real_rooms(&input);
+
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[0].sector_id, 343);
+        assert_eq!(rooms[0].name(), "very encrypted name");
+        assert_eq!(rooms[0].room, input[0]);
+        assert_eq!(rooms[1].words, vec!["northpole", "object", "storage"]);
+
+        let sectors = |rooms: Vec<&RealRoom>| rooms.iter().map(|r| r.sector_id).collect_vec();
+        assert_eq!(sectors(rooms_named(&rooms, "object").collect()), vec![394]);
+        assert_eq!(
+            sectors(rooms_where(&rooms, |room| room.words.len() == 3).collect()),
+            vec![343, 394]
+        );
+        assert_eq!(part2(&input), Ok(394));
+        assert!(part2(&input[..1]).is_err());
+    }
+
+    fn room() -> impl Strategy<Value = Room> {
+        (
+            prop::collection::vec("[a-z]{1,10}", 1..6),
+            any::<u16>(),
+            prop::array::uniform5(b'a'..=b'z'),
+        )
+            .prop_map(|(name, sector_id, checksum)| Room {
+                name,
+                sector_id,
+                checksum,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(room in room()) {
+            let text = room.to_string();
+            prop_assert_eq!(Room::from_str(&text).unwrap(), room);
+            prop_assert_eq!(Room::from_str(&text).unwrap().to_string(), text);
+        }
+
+        #[test]
+        fn decrypt_undoes_shift(room in room()) {
+            let back = 26 - (room.sector_id % 26) as u8;
+            let encrypted = Room {
+                name: room
+                    .name
+                    .iter()
+                    .map(|word| word.bytes().map(|b| rotate(b, back) as char).collect())
+                    .collect(),
+                ..room.clone()
+            };
+
+            prop_assert_eq!(encrypted.decrypt(), room.name);
+        }
+    }
 }
@@ -1,15 +1,18 @@
 use std::fmt::Write;
 
+use crate::hashing::{self, SaltedMd5};
+
 #[aoc(day05, part1)]
 fn part1(input: &str) -> String {
+    let hasher = SaltedMd5::new(input);
     let mut out = String::new();
     let mut i = 0_u64;
 
     while out.len() < 8 {
-        let digest = md5::compute(format!("{input}{i}"));
+        let digest = hasher.digest_index(i);
         i += 1;
 
-        if digest.0[..2].iter().any(|&d| d != 0) || (digest.0[2] & 0xF0) != 0 {
+        if !hashing::starts_with_zero_nibbles(&digest, 5) {
             continue;
         }
 
@@ -21,14 +24,15 @@ fn part1(input: &str) -> String {
 
 #[aoc(day05, part2)]
 fn part2(input: &str) -> String {
+    let hasher = SaltedMd5::new(input);
     let mut out = ['*'; 8];
     let mut i = 0_u64;
 
     while out.contains(&'*') {
-        let digest = md5::compute(format!("{input}{i}"));
+        let digest = hasher.digest_index(i);
         i += 1;
 
-        if digest.0[..2].iter().any(|&d| d != 0) || (digest.0[2] & 0xF0) != 0 {
+        if !hashing::starts_with_zero_nibbles(&digest, 5) {
             continue;
         }
 
@@ -48,6 +52,16 @@ fn part2(input: &str) -> String {
     out.iter().collect()
 }
 
+solution! {
+    Day05 {
+        day: 5,
+        title: "How About a Nice Game of Chess?",
+        input: String = String::from,
+        part1: String = part1,
+        part2: String = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +82,16 @@ fn part2(input: &Array2<u8>) -> String {
         .collect()
 }
 
+solution! {
+    Day06 {
+        day: 6,
+        title: "Signals and Noise",
+        input: Array2<u8> = parse,
+        part1: String = part1,
+        part2: String = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
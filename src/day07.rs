@@ -83,8 +83,20 @@ fn part2(input: &[Address]) -> usize {
     input.iter().filter(|addr| addr.supports_ssl()).count()
 }
 
+solution! {
+    Day07 {
+        day: 7,
+        title: "Internet Protocol Version 7",
+        input: Vec<Address> = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -130,4 +142,23 @@ mod tests {
     fn part2_example4() {
         assert!(Address::from_str("zazbz[bzb]cdb").unwrap().supports_ssl());
     }
+
+    // A small alphabet makes ABBA and ABA sequences common.
+    fn sequences() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec("[abc]{0,8}", 1..5)
+    }
+
+    proptest! {
+        #[test]
+        fn verdicts_ignore_sequence_order(supernet in sequences(), hypernet in sequences()) {
+            let address = Address { supernet, hypernet };
+            let reversed = Address {
+                supernet: address.supernet.iter().rev().cloned().collect(),
+                hypernet: address.hypernet.iter().rev().cloned().collect(),
+            };
+
+            prop_assert_eq!(reversed.supports_tls(), address.supports_tls());
+            prop_assert_eq!(reversed.supports_ssl(), address.supports_ssl());
+        }
+    }
 }
@@ -0,0 +1,52 @@
+use crate::assembunny::{Machine, ParseError, Program};
+
+fn run(program: &Program, c: i64) -> i64 {
+    let mut machine = Machine::new(&program.instructions, true);
+    machine.set_register(2, c);
+    machine.run();
+    machine.register(0)
+}
+
+#[aoc_generator(day12)]
+fn parse(input: &str) -> Result<Program, ParseError> {
+    input.parse()
+}
+
+#[aoc(day12, part1)]
+fn part1(input: &Program) -> i64 {
+    run(input, 0)
+}
+
+#[aoc(day12, part2)]
+fn part2(input: &Program) -> i64 {
+    run(input, 1)
+}
+
+solution! {
+    Day12 {
+        day: 12,
+        title: "Leonardo's Monorail",
+        input: Program = parse,
+        part1: i64 = part1,
+        part2: i64 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        cpy 41 a
+        inc a
+        inc a
+        dec a
+        jnz a 2
+        dec a
+    ";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 42);
+    }
+}
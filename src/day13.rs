@@ -0,0 +1,182 @@
+use std::{fmt::Write, num::ParseIntError};
+
+use fnv::FnvHashSet;
+
+use crate::search;
+
+const START: Position = Position(1, 1);
+const TARGET: Position = Position(31, 39);
+const MAX_STEPS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position(pub u32, pub u32);
+
+impl Position {
+    fn adjacent(self) -> impl Iterator<Item = Self> {
+        let Self(x, y) = self;
+
+        [
+            y.checked_sub(1).map(|y| Self(x, y)),
+            x.checked_sub(1).map(|x| Self(x, y)),
+            y.checked_add(1).map(|y| Self(x, y)),
+            x.checked_add(1).map(|x| Self(x, y)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// The maze is never stored: every cell is computed from the favourite number
+/// when it is looked at, so it extends as far as the coordinates go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Maze {
+    favourite: u32,
+}
+
+impl Maze {
+    pub fn new(favourite: u32) -> Self {
+        Self { favourite }
+    }
+
+    pub fn is_wall(self, Position(x, y): Position) -> bool {
+        // u128 holds the polynomial for any pair of u32 coordinates.
+        let (x, y) = (x as u128, y as u128);
+        let value = x * x + 3 * x + 2 * x * y + y + y * y + self.favourite as u128;
+        value.count_ones() % 2 == 1
+    }
+
+    fn open_neighbours(self, pos: Position) -> impl Iterator<Item = Position> {
+        pos.adjacent().filter(move |&p| !self.is_wall(p))
+    }
+
+    pub fn shortest_path(self, from: Position, to: Position) -> Option<Vec<Position>> {
+        if self.is_wall(from) || self.is_wall(to) {
+            return None;
+        }
+
+        search::bfs(from, |&pos| self.open_neighbours(pos), |&pos| pos == to)
+            .found
+            .map(|found| found.path)
+    }
+
+    pub fn reachable_within(self, from: Position, max_steps: usize) -> usize {
+        if self.is_wall(from) {
+            return 0;
+        }
+
+        search::reachable_within(from, |&pos| self.open_neighbours(pos), max_steps).len()
+    }
+
+    pub fn render(self, width: u32, height: u32, path: &[Position]) -> String {
+        let path: FnvHashSet<_> = path.iter().copied().collect();
+        let mut out = String::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                let pos = Position(x, y);
+                let c = if path.contains(&pos) {
+                    'O'
+                } else if self.is_wall(pos) {
+                    '#'
+                } else {
+                    '.'
+                };
+
+                write!(out, "{c}").expect("Write to string failed");
+            }
+
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+#[aoc_generator(day13)]
+fn parse(input: &str) -> Result<Maze, ParseIntError> {
+    Ok(Maze::new(input.trim().parse()?))
+}
+
+#[aoc(day13, part1)]
+fn part1(maze: &Maze) -> Result<usize, String> {
+    let path = maze
+        .shortest_path(START, TARGET)
+        .ok_or("No path to the target")?;
+    Ok(path.len() - 1)
+}
+
+#[aoc(day13, part2)]
+fn part2(maze: &Maze) -> usize {
+    maze.reachable_within(START, MAX_STEPS)
+}
+
+solution! {
+    Day13 {
+        day: 13,
+        title: "A Maze of Twisty Little Cubicles",
+        input: Maze = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: Maze = Maze { favourite: 10 };
+
+    #[test]
+    fn part1_example() {
+        let path = EXAMPLE.shortest_path(START, Position(7, 4)).unwrap();
+        assert_eq!(path.len() - 1, 11);
+    }
+
+    #[test]
+    fn render_example() {
+        assert_eq!(
+            EXAMPLE.render(10, 7, &[]),
+            "\
+.#.####.##
+..#..#...#
+#....##...
+###.#.###.
+.##..#..#.
+..##....#.
+#...##.###
+"
+        );
+    }
+
+    #[test]
+    fn render_example_path() {
+        let path = EXAMPLE.shortest_path(START, Position(7, 4)).unwrap();
+        assert_eq!(
+            EXAMPLE.render(10, 7, &path),
+            "\
+.#.####.##
+.O#..#...#
+#OOO.##...
+###O#.###.
+.##OO#OO#.
+..##OOO.#.
+#...##.###
+"
+        );
+    }
+
+    #[test]
+    fn far_corner() {
+        let corner = Position(u32::MAX, u32::MAX);
+        let (x, y) = (u32::MAX as u128, u32::MAX as u128);
+        let value = x * x + 3 * x + 2 * x * y + y + y * y + 10;
+        assert_eq!(EXAMPLE.is_wall(corner), value.count_ones() % 2 == 1);
+        assert!(EXAMPLE.open_neighbours(corner).count() <= 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(EXAMPLE.reachable_within(START, 0), 1);
+        assert_eq!(EXAMPLE.reachable_within(START, 2), 5);
+    }
+}
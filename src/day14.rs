@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use rayon::prelude::*;
+
+use crate::hashing::{self, SaltedMd5};
+
+const KEY_COUNT: usize = 64;
+const LOOKAHEAD: u64 = 1000;
+const STRETCH: usize = 2016;
+const BATCH_SIZE: u64 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct HashInfo {
+    triple: Option<u8>,
+    quintuples: u16,
+}
+
+impl HashInfo {
+    fn from_hex(hex: &[u8; 32]) -> Self {
+        let nibbles = hex.map(|c| match c {
+            b'0'..=b'9' => c - b'0',
+            _ => c - b'a' + 10,
+        });
+
+        let triple = nibbles
+            .windows(3)
+            .find(|w| w[0] == w[1] && w[1] == w[2])
+            .map(|w| w[0]);
+
+        let quintuples = nibbles
+            .windows(5)
+            .filter(|w| w.iter().all(|&n| n == w[0]))
+            .fold(0, |mask, w| mask | (1 << w[0]));
+
+        Self { triple, quintuples }
+    }
+}
+
+struct KeyFinder {
+    hasher: SaltedMd5,
+    stretch: usize,
+    cache: VecDeque<HashInfo>,
+    first_index: u64,
+}
+
+impl KeyFinder {
+    fn new(salt: &str, stretch: usize) -> Self {
+        Self {
+            hasher: SaltedMd5::new(salt),
+            stretch,
+            cache: VecDeque::new(),
+            first_index: 0,
+        }
+    }
+
+    fn compute(&self, index: u64) -> HashInfo {
+        let mut hex = hashing::to_hex(&self.hasher.digest_index(index));
+        for _ in 0..self.stretch {
+            hex = hashing::to_hex(&md5::compute(hex));
+        }
+
+        HashInfo::from_hex(&hex)
+    }
+
+    fn info(&mut self, index: u64) -> HashInfo {
+        let offset = (index - self.first_index) as usize;
+
+        while self.cache.len() <= offset {
+            let start = self.first_index + self.cache.len() as u64;
+            let batch: Vec<_> = (start..start + BATCH_SIZE)
+                .into_par_iter()
+                .map(|i| self.compute(i))
+                .collect();
+
+            self.cache.extend(batch);
+        }
+
+        self.cache[offset]
+    }
+
+    fn forget_before(&mut self, index: u64) {
+        while self.first_index < index && self.cache.pop_front().is_some() {
+            self.first_index += 1;
+        }
+    }
+
+    fn is_key(&mut self, index: u64) -> bool {
+        let Some(triple) = self.info(index).triple else {
+            return false;
+        };
+
+        (index + 1..=index + LOOKAHEAD).any(|i| self.info(i).quintuples & (1 << triple) != 0)
+    }
+
+    fn keys(mut self) -> impl Iterator<Item = u64> {
+        (0..).filter(move |&index| {
+            self.forget_before(index);
+            self.is_key(index)
+        })
+    }
+}
+
+fn nth_key_index(salt: &str, stretch: usize, n: usize) -> Result<u64, String> {
+    KeyFinder::new(salt.trim(), stretch)
+        .keys()
+        .nth(n - 1)
+        .ok_or_else(|| format!("Ran out of indices before key {n}"))
+}
+
+#[aoc(day14, part1)]
+fn part1(input: &str) -> Result<u64, String> {
+    nth_key_index(input, 0, KEY_COUNT)
+}
+
+#[aoc(day14, part2)]
+fn part2(input: &str) -> Result<u64, String> {
+    nth_key_index(input, STRETCH, KEY_COUNT)
+}
+
+solution! {
+    Day14 {
+        day: 14,
+        title: "One-Time Pad",
+        input: String = String::from,
+        part1: u64 = part1,
+        part2: u64 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_info_example() {
+        let hex = hashing::to_hex(&md5::compute("abc18"));
+        assert_eq!(HashInfo::from_hex(&hex).triple, Some(8));
+    }
+
+    #[test]
+    fn stretched_hash_example() {
+        let finder = KeyFinder::new("abc", STRETCH);
+        let mut hex = hashing::to_hex(&md5::compute("abc0"));
+        for _ in 0..STRETCH {
+            hex = hashing::to_hex(&md5::compute(hex));
+        }
+
+        assert_eq!(&hex, b"a107ff634856bb300138cac6568c0f24");
+        assert_eq!(finder.compute(0), HashInfo::from_hex(&hex));
+    }
+
+    #[test]
+    fn part1_example() {
+        let mut keys = KeyFinder::new("abc", 0).keys();
+        assert_eq!(keys.next(), Some(39));
+        assert_eq!(keys.next(), Some(92));
+        assert_eq!(part1("abc"), Ok(22728));
+    }
+
+    #[test]
+    #[ignore = "Brute force MD5"]
+    fn part2_example() {
+        assert_eq!(KeyFinder::new("abc", STRETCH).keys().next(), Some(10));
+        assert_eq!(part2("abc"), Ok(22551));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{error, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::math;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Disc {
+    number: i64,
+    positions: i64,
+    start: i64,
+}
+
+impl Disc {
+    // The capsule reaches this disc `number` seconds after being dropped at `t`,
+    // and must find it at position 0.
+    fn congruence(self) -> (i64, i64) {
+        (-(self.start + self.number), self.positions)
+    }
+}
+
+impl FromStr for Disc {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("{s:?} does not look like a disc description");
+
+        let rest = s.trim().strip_prefix("Disc #").ok_or_else(err)?;
+        let (number, rest) = rest.split_once(" has ").ok_or_else(err)?;
+        let (positions, rest) = rest
+            .split_once(" positions; at time=0, it is at position ")
+            .ok_or_else(err)?;
+        let start = rest.strip_suffix('.').ok_or_else(err)?;
+
+        let disc = Self {
+            number: number.parse()?,
+            positions: positions.parse()?,
+            start: start.parse()?,
+        };
+
+        if disc.positions <= 0 {
+            return Err(format!("Disc #{} must have at least one position", disc.number).into());
+        }
+
+        Ok(disc)
+    }
+}
+
+#[aoc_generator(day15)]
+fn parse(input: &str) -> Result<Vec<Disc>, Box<dyn error::Error>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::parse)
+        .try_collect()
+}
+
+fn first_drop_time(discs: &[Disc]) -> Option<i64> {
+    let congruences = discs.iter().map(|d| d.congruence()).collect_vec();
+    math::crt(&congruences).map(|(t, _)| t)
+}
+
+#[aoc(day15, part1)]
+fn part1(input: &[Disc]) -> Result<i64, String> {
+    first_drop_time(input).ok_or_else(|| "The discs never line up".to_string())
+}
+
+#[aoc(day15, part2)]
+fn part2(input: &[Disc]) -> Result<i64, String> {
+    let mut discs = input.to_vec();
+    discs.push(Disc {
+        number: discs.len() as i64 + 1,
+        positions: 11,
+        start: 0,
+    });
+
+    first_drop_time(&discs).ok_or_else(|| "The discs never line up".to_string())
+}
+
+solution! {
+    Day15 {
+        day: 15,
+        title: "Timing is Everything",
+        input: Vec<Disc> = parse,
+        part1: i64 = part1,
+        part2: i64 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        Disc #1 has 5 positions; at time=0, it is at position 4.
+        Disc #2 has 2 positions; at time=0, it is at position 1.
+    ";
+
+    fn brute_force(discs: &[Disc]) -> Option<i64> {
+        let period: i64 = discs.iter().map(|d| d.positions).product();
+
+        (0..period).find(|t| {
+            discs
+                .iter()
+                .all(|d| (d.start + d.number + t) % d.positions == 0)
+        })
+    }
+
+    #[test]
+    fn part1_example() {
+        let discs = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&discs), Ok(5));
+        assert_eq!(brute_force(&discs), Some(5));
+    }
+
+    #[test]
+    fn crt_matches_brute_force() {
+        for positions in [[2, 3, 4], [4, 6, 9], [5, 5, 7], [6, 10, 15]] {
+            for start in 0..positions[0] {
+                let discs = positions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &p)| Disc {
+                        number: i as i64 + 1,
+                        positions: p,
+                        start: (start * (i as i64 + 2)) % p,
+                    })
+                    .collect_vec();
+
+                assert_eq!(first_drop_time(&discs), brute_force(&discs));
+            }
+        }
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(parse("Disc #1 has five positions; at time=0, it is at position 4.").is_err());
+        assert!(parse("Disc #1 has 0 positions; at time=0, it is at position 0.").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::ops::Range;
+
+const DISK1: usize = 272;
+const DISK2: usize = 35_651_584;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct Bits {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bits {
+    fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(bits.div_ceil(64)),
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> bool {
+        (self.words[index / 64] >> (index % 64)) & 1 == 1
+    }
+
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+
+        if bit {
+            self.words[self.len / 64] |= 1 << (self.len % 64);
+        }
+
+        self.len += 1;
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        self.len = len;
+        self.words.truncate(len.div_ceil(64));
+        if !len.is_multiple_of(64) {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (len % 64)) - 1;
+        }
+    }
+
+    fn count_ones(&self, range: Range<usize>) -> u32 {
+        let (start, end) = (range.start, range.end);
+        if start >= end {
+            return 0;
+        }
+
+        let (first_word, last_word) = (start / 64, (end - 1) / 64);
+        let low_mask = u64::MAX << (start % 64);
+        let high_mask = u64::MAX >> (63 - (end - 1) % 64);
+
+        if first_word == last_word {
+            return (self.words[first_word] & low_mask & high_mask).count_ones();
+        }
+
+        (self.words[first_word] & low_mask).count_ones()
+            + self.words[first_word + 1..last_word]
+                .iter()
+                .map(|w| w.count_ones())
+                .sum::<u32>()
+            + (self.words[last_word] & high_mask).count_ones()
+    }
+}
+
+#[aoc_generator(day16)]
+fn parse_bits(input: &str) -> Result<Bits, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("The initial state is empty".to_string());
+    }
+
+    let mut bits = Bits::with_capacity(input.len());
+    for c in input.bytes() {
+        match c {
+            b'0' => bits.push(false),
+            b'1' => bits.push(true),
+            _ => {
+                return Err(format!(
+                    "Invalid character in initial state: {:?}",
+                    c as char
+                ));
+            }
+        }
+    }
+
+    Ok(bits)
+}
+
+fn dragon_curve(mut bits: Bits, length: usize) -> Bits {
+    debug_assert!(bits.len() > 0, "Parsing rejects an empty initial state");
+
+    bits.words.reserve(length.div_ceil(64));
+
+    while bits.len() < length {
+        let len = bits.len();
+        bits.push(false);
+        for i in (0..len).rev() {
+            let bit = bits.get(i);
+            bits.push(!bit);
+        }
+    }
+
+    bits.truncate(length);
+    bits
+}
+
+// Repeatedly XNOR-ing pairs of a block of 2^k bits collapses to a single bit
+// that is set iff the block holds an even number of ones, so the checksum can
+// be read straight off popcounts of each block.
+fn checksum(bits: &Bits) -> String {
+    let len = bits.len();
+    if len == 0 {
+        return String::new();
+    }
+
+    let block = 1 << len.trailing_zeros();
+    debug_assert!(block > 1, "Disk length must be even to have a checksum");
+
+    (0..len)
+        .step_by(block)
+        .map(|start| {
+            if bits.count_ones(start..start + block).is_multiple_of(2) {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect()
+}
+
+pub fn disk_checksum(initial: &str, length: usize) -> Result<String, String> {
+    if length == 0 || !length.is_multiple_of(2) {
+        return Err(format!(
+            "Disk length must be even and positive (got {length})"
+        ));
+    }
+
+    Ok(checksum(&dragon_curve(parse_bits(initial)?, length)))
+}
+
+#[aoc(day16, part1)]
+fn part1(initial: &Bits) -> String {
+    checksum(&dragon_curve(initial.clone(), DISK1))
+}
+
+#[aoc(day16, part2)]
+fn part2(initial: &Bits) -> String {
+    checksum(&dragon_curve(initial.clone(), DISK2))
+}
+
+solution! {
+    Day16 {
+        day: 16,
+        title: "Dragon Checksum",
+        input: Bits = parse_bits,
+        part1: String = part1,
+        part2: String = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(bits: &Bits) -> String {
+        (0..bits.len())
+            .map(|i| if bits.get(i) { '1' } else { '0' })
+            .collect()
+    }
+
+    fn naive_checksum(data: &str) -> String {
+        let mut data = data.as_bytes().to_vec();
+
+        while data.len().is_multiple_of(2) {
+            data = data
+                .chunks(2)
+                .map(|p| if p[0] == p[1] { b'1' } else { b'0' })
+                .collect();
+        }
+
+        String::from_utf8(data).unwrap()
+    }
+
+    #[test]
+    fn dragon_curve_examples() {
+        for (input, output) in [
+            ("1", "100"),
+            ("0", "001"),
+            ("11111", "11111000000"),
+            ("111100001010", "1111000010100101011110000"),
+        ] {
+            assert_eq!(
+                to_string(&dragon_curve(parse_bits(input).unwrap(), output.len())),
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn checksum_example() {
+        assert_eq!(checksum(&parse_bits("110010110100").unwrap()), "100");
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(disk_checksum("10000", 20).unwrap(), "01100");
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(parse_bits("").is_err());
+        assert!(parse_bits("10201").is_err());
+        assert!(disk_checksum("10000", 21).is_err());
+        assert!(disk_checksum("10000", 0).is_err());
+    }
+
+    #[test]
+    fn checksum_matches_naive() {
+        for length in (2..300).step_by(2) {
+            let bits = dragon_curve(parse_bits("10111100110001111").unwrap(), length);
+            assert_eq!(checksum(&bits), naive_checksum(&to_string(&bits)));
+        }
+    }
+
+    #[test]
+    fn count_ones_ranges() {
+        let bits = dragon_curve(parse_bits("1").unwrap(), 200);
+        let s = to_string(&bits);
+
+        for start in 0..200 {
+            for end in start..=200 {
+                let expected = s[start..end].bytes().filter(|&b| b == b'1').count() as u32;
+                assert_eq!(bits.count_ones(start..end), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    hashing::{self, SaltedMd5},
+    search,
+};
+
+const SIZE: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    // Same order as the first four characters of the hash.
+    const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    fn letter(self) -> u8 {
+        match self {
+            Self::Up => b'U',
+            Self::Down => b'D',
+            Self::Left => b'L',
+            Self::Right => b'R',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Room(u8, u8);
+
+impl Room {
+    const START: Self = Self(0, 0);
+    const VAULT: Self = Self(SIZE - 1, SIZE - 1);
+
+    fn step(self, direction: Direction) -> Option<Self> {
+        let Self(x, y) = self;
+        match direction {
+            Direction::Up if y > 0 => Some(Self(x, y - 1)),
+            Direction::Down if y < SIZE - 1 => Some(Self(x, y + 1)),
+            Direction::Left if x > 0 => Some(Self(x - 1, y)),
+            Direction::Right if x < SIZE - 1 => Some(Self(x + 1, y)),
+            _ => None,
+        }
+    }
+}
+
+struct Vault {
+    passcode: SaltedMd5,
+}
+
+impl Vault {
+    fn new(passcode: &str) -> Self {
+        Self {
+            passcode: SaltedMd5::new(passcode.trim()),
+        }
+    }
+
+    fn moves(&self, room: Room, path: &[u8]) -> impl Iterator<Item = (Direction, Room)> {
+        let digest = self.passcode.digest(path);
+
+        Direction::ALL
+            .into_iter()
+            .enumerate()
+            .filter(move |&(i, _)| hashing::nibble(&digest, i) >= 0xb)
+            .filter_map(move |(_, dir)| room.step(dir).map(|next| (dir, next)))
+    }
+
+    fn shortest_path(&self) -> Option<String> {
+        // The doors depend on the whole path, so it is part of the state.
+        let neighbours = |(room, path): &(Room, Vec<u8>)| {
+            self.moves(*room, path)
+                .map(|(dir, next)| (next, [path.as_slice(), &[dir.letter()]].concat()))
+                .collect::<Vec<_>>()
+        };
+
+        let found = search::bfs((Room::START, Vec::new()), neighbours, |(room, _)| {
+            *room == Room::VAULT
+        })
+        .found?;
+
+        let (_, path) = found
+            .path
+            .into_iter()
+            .last()
+            .expect("Path ends at the vault");
+        Some(String::from_utf8(path).expect("Path is ASCII"))
+    }
+
+    fn longest_path_len(&self) -> Option<usize> {
+        fn explore(vault: &Vault, room: Room, path: &mut Vec<u8>) -> Option<usize> {
+            if room == Room::VAULT {
+                return Some(path.len());
+            }
+
+            let mut longest = None;
+            for (dir, next) in vault.moves(room, path).collect::<Vec<_>>() {
+                path.push(dir.letter());
+                longest = longest.max(explore(vault, next, path));
+                path.pop();
+            }
+
+            longest
+        }
+
+        explore(self, Room::START, &mut Vec::new())
+    }
+}
+
+#[aoc(day17, part1)]
+fn part1(input: &str) -> Result<String, String> {
+    Vault::new(input)
+        .shortest_path()
+        .ok_or_else(|| "No path to the vault".to_string())
+}
+
+#[aoc(day17, part2)]
+fn part2(input: &str) -> Result<usize, String> {
+    Vault::new(input)
+        .longest_path_len()
+        .ok_or_else(|| "No path to the vault".to_string())
+}
+
+solution! {
+    Day17 {
+        day: 17,
+        title: "Two Steps Forward",
+        input: String = String::from,
+        part1: String = part1,
+        part2: usize = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_end() {
+        let vault = Vault::new("hijkl");
+        assert_eq!(vault.shortest_path(), None);
+        assert_eq!(vault.longest_path_len(), None);
+        assert_eq!(part1("hijkl"), Err("No path to the vault".to_string()));
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1("ihgpwlah"), Ok("DDRRRD".into()));
+        assert_eq!(part1("kglvqrro"), Ok("DDUDRLRRUDRD".into()));
+        assert_eq!(
+            part1("ulqzkmiv"),
+            Ok("DRURDRUDDLLDLUURRDULRLDUUDDDRR".into())
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2("ihgpwlah"), Ok(370));
+        assert_eq!(part2("kglvqrro"), Ok(492));
+        assert_eq!(part2("ulqzkmiv"), Ok(830));
+    }
+}
@@ -0,0 +1,207 @@
+use std::{error, str::FromStr};
+
+const ROWS1: usize = 40;
+const ROWS2: usize = 400_000;
+/// The Wolfram rule the puzzle's trap tiles follow.
+pub const TRAP_RULE: u8 = 90;
+
+// Bit `i` is tile `i` counting from the left, set for a trap. Tiles past either
+// edge are always safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Row {
+    bits: u128,
+    width: u32,
+}
+
+impl Row {
+    fn mask(self) -> u128 {
+        u128::MAX >> (u128::BITS - self.width)
+    }
+
+    pub fn width(self) -> u32 {
+        self.width
+    }
+
+    pub fn is_trap(self, tile: u32) -> bool {
+        tile < self.width && self.bits >> tile & 1 == 1
+    }
+
+    pub fn safe_tiles(self) -> u32 {
+        self.width - self.bits.count_ones()
+    }
+
+    fn with_bits(self, bits: u128) -> Self {
+        Self {
+            bits: bits & self.mask(),
+            width: self.width,
+        }
+    }
+
+    fn left_neighbours(self) -> u128 {
+        self.bits << 1
+    }
+
+    fn right_neighbours(self) -> u128 {
+        self.bits >> 1
+    }
+}
+
+impl FromStr for Row {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s.len() > u128::BITS as usize {
+            return Err(format!("Row must be 1 to 128 tiles wide (got {})", s.len()).into());
+        }
+
+        let mut bits = 0;
+        for (i, c) in s.bytes().enumerate() {
+            match c {
+                b'^' => bits |= 1 << i,
+                b'.' => (),
+                _ => return Err(format!("Invalid tile {:?} in {s:?}", c as char).into()),
+            }
+        }
+
+        Ok(Self {
+            bits,
+            width: s.len() as u32,
+        })
+    }
+}
+
+/// An elementary cellular automaton for any Wolfram rule number, where traps
+/// are live cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Automaton {
+    pub rule: u8,
+}
+
+impl Automaton {
+    pub fn new(rule: u8) -> Self {
+        Self { rule }
+    }
+
+    pub fn step(self, row: Row) -> Row {
+        if self.rule == TRAP_RULE {
+            return row.with_bits(row.left_neighbours() ^ row.right_neighbours());
+        }
+
+        let (left, centre, right) = (row.left_neighbours(), row.bits, row.right_neighbours());
+        let pick = |bits: u128, set: bool| if set { bits } else { !bits };
+
+        let next = (0..8)
+            .filter(|pattern| self.rule >> pattern & 1 == 1)
+            .map(|pattern| {
+                pick(left, pattern & 0b100 != 0)
+                    & pick(centre, pattern & 0b010 != 0)
+                    & pick(right, pattern & 0b001 != 0)
+            })
+            .fold(0, |acc, cells| acc | cells);
+
+        row.with_bits(next)
+    }
+
+    /// `first` followed by every row it grows into.
+    pub fn rows(self, first: Row) -> impl Iterator<Item = Row> {
+        std::iter::successors(Some(first), move |&row| Some(self.step(row)))
+    }
+
+    /// Safe tiles across the first `rows` rows, `first` included.
+    pub fn safe_tiles(self, first: Row, rows: usize) -> u64 {
+        self.rows(first)
+            .take(rows)
+            .map(|row| row.safe_tiles() as u64)
+            .sum()
+    }
+}
+
+#[aoc_generator(day18)]
+fn parse(input: &str) -> Result<Row, Box<dyn error::Error>> {
+    input.parse()
+}
+
+#[aoc(day18, part1)]
+fn part1(input: &Row) -> u64 {
+    Automaton::new(TRAP_RULE).safe_tiles(*input, ROWS1)
+}
+
+#[aoc(day18, part2)]
+fn part2(input: &Row) -> u64 {
+    Automaton::new(TRAP_RULE).safe_tiles(*input, ROWS2)
+}
+
+solution! {
+    Day18 {
+        day: 18,
+        title: "Like a Rogue",
+        input: Row = parse,
+        part1: u64 = part1,
+        part2: u64 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_step(rule: u8, row: &[bool]) -> Vec<bool> {
+        (0..row.len())
+            .map(|i| {
+                let left = i > 0 && row[i - 1];
+                let right = i + 1 < row.len() && row[i + 1];
+                let pattern = (left as u8) << 2 | (row[i] as u8) << 1 | right as u8;
+                rule >> pattern & 1 == 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn part1_example() {
+        let automaton = Automaton { rule: TRAP_RULE };
+        assert_eq!(automaton.safe_tiles(parse("..^^.").unwrap(), 3), 6);
+        assert_eq!(automaton.safe_tiles(parse(".^^.^.^^^^").unwrap(), 10), 38);
+    }
+
+    #[test]
+    fn example_rows() {
+        let rows: Vec<_> = Automaton { rule: TRAP_RULE }
+            .rows(parse("..^^.").unwrap())
+            .take(3)
+            .collect();
+        assert_eq!(rows[1], parse(".^^^^").unwrap());
+        assert_eq!(rows[2], parse("^^..^").unwrap());
+        assert!(rows[2].is_trap(0) && !rows[2].is_trap(2) && !rows[2].is_trap(5));
+        assert_eq!(rows[2].width(), 5);
+    }
+
+    #[test]
+    fn generic_rules_match_naive() {
+        let first = ".^^.^.^^^^..^^^.^...^.^^^^.^^.";
+        for rule in 0..=255 {
+            let automaton = Automaton { rule };
+            let mut row = parse(first).unwrap();
+            let mut naive: Vec<_> = first.bytes().map(|b| b == b'^').collect();
+
+            for _ in 0..20 {
+                row = automaton.step(row);
+                naive = naive_step(rule, &naive);
+
+                let expected = naive
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, &trap)| acc | (trap as u128) << i);
+                assert_eq!(row.bits, expected, "rule {rule}");
+            }
+        }
+    }
+
+    #[test]
+    fn full_width_row() {
+        let row = parse(&"^".repeat(128)).unwrap();
+        assert_eq!(row.safe_tiles(), 0);
+        assert_eq!(Automaton { rule: TRAP_RULE }.step(row).safe_tiles(), 126);
+        assert!(parse(&".".repeat(129)).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::VecDeque,
+    num::{NonZeroUsize, ParseIntError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Steal {
+    Left,
+    Across,
+}
+
+impl Steal {
+    pub fn victim_offset(self, remaining: usize) -> usize {
+        match self {
+            Self::Left => 1,
+            Self::Across => remaining / 2,
+        }
+    }
+
+    /// The elf left with every present, or `None` for an empty circle.
+    pub fn winner(self, elves: usize) -> Option<usize> {
+        Some(match self {
+            Self::Left => {
+                let power = 1 << elves.checked_ilog2()?;
+                2 * (elves - power) + 1
+            }
+            Self::Across => {
+                let power = 3_usize.pow(elves.checked_ilog(3)?);
+                if elves == power {
+                    elves
+                } else if elves <= 2 * power {
+                    elves - power
+                } else {
+                    2 * elves - 3 * power
+                }
+            }
+        })
+    }
+
+    pub fn simulate(self, elves: usize) -> Vec<usize> {
+        match self {
+            Self::Left => simulate_left(elves),
+            Self::Across => simulate_across(elves),
+        }
+    }
+}
+
+fn simulate_left(elves: usize) -> Vec<usize> {
+    let mut circle: VecDeque<_> = (1..=elves).collect();
+    let mut order = Vec::with_capacity(elves);
+
+    while circle.len() > 1 {
+        let current = circle.pop_front().expect("Circle is not empty");
+        order.extend(circle.pop_front());
+        circle.push_back(current);
+    }
+
+    order.extend(circle);
+    order
+}
+
+// `left` starts with the current elf and `right` starts with the one across the
+// circle, so both ends of the steal are always at the front of a deque.
+fn simulate_across(elves: usize) -> Vec<usize> {
+    let mut left: VecDeque<_> = (1..=elves / 2).collect();
+    let mut right: VecDeque<_> = (elves / 2 + 1..=elves).collect();
+    let mut order = Vec::with_capacity(elves);
+
+    while left.len() + right.len() > 1 {
+        if left.len() > right.len() {
+            order.extend(left.pop_back());
+        } else {
+            order.extend(right.pop_front());
+        }
+
+        right.extend(left.pop_front());
+        left.extend(right.pop_front());
+    }
+
+    order.extend(left);
+    order.extend(right);
+    order
+}
+
+// Fenwick tree over the elves still in the circle, used to find the elf at a
+// given rank among the remaining ones.
+struct Circle {
+    tree: Vec<usize>,
+}
+
+impl Circle {
+    fn new(elves: usize) -> Self {
+        let mut tree = vec![0; elves + 1];
+        for i in 1..=elves {
+            tree[i] += 1;
+            let parent = i + (i & i.wrapping_neg());
+            if parent <= elves {
+                tree[parent] += tree[i];
+            }
+        }
+
+        Self { tree }
+    }
+
+    fn remove(&mut self, elf: usize) {
+        let mut i = elf;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn nth(&self, rank: usize) -> usize {
+        let mut pos = 0;
+        let mut remaining = rank + 1;
+        let mut step = (self.tree.len() - 1)
+            .checked_ilog2()
+            .map_or(0, |log| 1 << log);
+
+        while step > 0 {
+            if pos + step < self.tree.len() && self.tree[pos + step] < remaining {
+                pos += step;
+                remaining -= self.tree[pos];
+            }
+            step >>= 1;
+        }
+
+        pos + 1
+    }
+}
+
+pub fn elimination_order<F: Fn(usize) -> usize>(elves: usize, victim_offset: F) -> Vec<usize> {
+    let mut circle = Circle::new(elves);
+    let mut order = Vec::with_capacity(elves);
+    let mut current = 0;
+
+    for remaining in (2..=elves).rev() {
+        let offset = victim_offset(remaining);
+        assert!(
+            (1..remaining).contains(&offset),
+            "Victim offset {offset} out of range for {remaining} elves"
+        );
+
+        let victim = (current + offset) % remaining;
+        let elf = circle.nth(victim);
+        circle.remove(elf);
+        order.push(elf);
+
+        let next = if victim > current {
+            current + 1
+        } else {
+            current
+        };
+        current = next % (remaining - 1);
+    }
+
+    if elves > 0 {
+        order.push(circle.nth(0));
+    }
+
+    order
+}
+
+#[aoc_generator(day19)]
+fn parse(input: &str) -> Result<usize, ParseIntError> {
+    input.trim().parse().map(NonZeroUsize::get)
+}
+
+#[aoc(day19, part1)]
+fn part1(input: &usize) -> Result<usize, &'static str> {
+    Steal::Left.winner(*input).ok_or("The circle has no elves")
+}
+
+#[aoc(day19, part2)]
+fn part2(input: &usize) -> Result<usize, &'static str> {
+    Steal::Across
+        .winner(*input)
+        .ok_or("The circle has no elves")
+}
+
+solution! {
+    Day19 {
+        day: 19,
+        title: "An Elephant Named Joseph",
+        input: usize = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&5), Ok(3));
+        assert_eq!(simulate_left(5), vec![2, 4, 1, 5, 3]);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&5), Ok(2));
+        assert_eq!(simulate_across(5), vec![3, 5, 1, 4, 2]);
+    }
+
+    #[test]
+    fn closed_form_matches_simulation() {
+        for rule in [Steal::Left, Steal::Across] {
+            for elves in 0..=3000 {
+                let order = rule.simulate(elves);
+                assert_eq!(
+                    order.last().copied(),
+                    rule.winner(elves),
+                    "{rule:?} {elves}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn empty_circle() {
+        assert!(parse("0").is_err());
+        assert_eq!(parse("7\n"), Ok(7));
+        assert!(part1(&0).is_err());
+    }
+
+    #[test]
+    fn general_solver_matches_simulation() {
+        for rule in [Steal::Left, Steal::Across] {
+            for elves in 1..=1000 {
+                assert_eq!(
+                    elimination_order(elves, |remaining| rule.victim_offset(remaining)),
+                    rule.simulate(elves),
+                    "{rule:?} {elves}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn general_solver_other_rule() {
+        // Steal from the second elf to the left while there is one.
+        assert_eq!(
+            elimination_order(7, |remaining| 2.min(remaining - 1)),
+            vec![3, 5, 7, 2, 6, 1, 4]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::{error, fmt, num::ParseIntError};
+
+use crate::interval::IntervalSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BlocklistError {
+    Malformed { line: usize },
+    InvalidNumber { line: usize, source: ParseIntError },
+    Inverted { line: usize, start: u32, end: u32 },
+}
+
+impl fmt::Display for BlocklistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { line } => write!(f, "Line {line}: expected 'start-end'"),
+            Self::InvalidNumber { line, source } => write!(f, "Line {line}: {source}"),
+            Self::Inverted { line, start, end } => {
+                write!(f, "Line {line}: range {start}-{end} ends before it starts")
+            }
+        }
+    }
+}
+
+impl error::Error for BlocklistError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[aoc_generator(day20)]
+fn parse(input: &str) -> Result<IntervalSet<u32>, BlocklistError> {
+    let mut blocked = IntervalSet::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let line_no = i + 1;
+        let (start, end) = line
+            .split_once('-')
+            .ok_or(BlocklistError::Malformed { line: line_no })?;
+        let number = |s: &str| {
+            s.trim()
+                .parse::<u32>()
+                .map_err(|source| BlocklistError::InvalidNumber {
+                    line: line_no,
+                    source,
+                })
+        };
+
+        let (start, end) = (number(start)?, number(end)?);
+        if start > end {
+            return Err(BlocklistError::Inverted {
+                line: line_no,
+                start,
+                end,
+            });
+        }
+
+        blocked.insert(start..=end);
+    }
+
+    Ok(blocked)
+}
+
+#[aoc(day20, part1)]
+fn part1(blocked: &IntervalSet<u32>) -> Result<u32, String> {
+    blocked
+        .first_gap(0..=u32::MAX)
+        .ok_or_else(|| "Every address is blocked".to_string())
+}
+
+#[aoc(day20, part2)]
+fn part2(blocked: &IntervalSet<u32>) -> u128 {
+    blocked.uncovered_count(0..=u32::MAX)
+}
+
+solution! {
+    Day20 {
+        day: 20,
+        title: "Firewall Rules",
+        input: IntervalSet<u32> = parse,
+        part1: u32 = part1,
+        part2: u128 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        5-8
+        0-2
+        4-7
+    ";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), Ok(3));
+    }
+
+    #[test]
+    fn part2_example() {
+        let blocked = parse(EXAMPLE).unwrap();
+        assert_eq!(blocked.uncovered_count(0..=9), 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            parse("0-2\n8-5"),
+            Err(BlocklistError::Inverted {
+                line: 2,
+                start: 8,
+                end: 5
+            })
+        );
+        assert_eq!(parse("1 2"), Err(BlocklistError::Malformed { line: 1 }));
+        assert!(matches!(
+            parse("1-x"),
+            Err(BlocklistError::InvalidNumber { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use std::{error, fmt, str::FromStr};
+
+use itertools::Itertools;
+
+const PASSWORD: &str = "abcdefgh";
+const SCRAMBLED: &str = "fbgdceah";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScrambleError {
+    MissingLetter(u8),
+    OutOfRange(usize),
+    Ambiguous { letter: u8, candidates: usize },
+    NoPreimage { letter: u8 },
+}
+
+impl fmt::Display for ScrambleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLetter(l) => write!(f, "Letter {:?} is not in the password", *l as char),
+            Self::OutOfRange(p) => write!(f, "Position {p} is past the end of the password"),
+            Self::Ambiguous { letter, candidates } => write!(
+                f,
+                "Rotating based on {:?} has {candidates} possible inputs",
+                *letter as char
+            ),
+            Self::NoPreimage { letter } => write!(
+                f,
+                "No input rotates based on {:?} to this password",
+                *letter as char
+            ),
+        }
+    }
+}
+
+impl error::Error for ScrambleError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Operation {
+    SwapPosition(usize, usize),
+    SwapLetter(u8, u8),
+    RotateLeft(usize),
+    RotateRight(usize),
+    RotateOnLetter(u8),
+    Reverse(usize, usize),
+    Move(usize, usize),
+}
+
+fn position(password: &[u8], letter: u8) -> Result<usize, ScrambleError> {
+    password
+        .iter()
+        .position(|&c| c == letter)
+        .ok_or(ScrambleError::MissingLetter(letter))
+}
+
+fn check_range(password: &[u8], positions: &[usize]) -> Result<(), ScrambleError> {
+    match positions.iter().find(|&&p| p >= password.len()) {
+        Some(&p) => Err(ScrambleError::OutOfRange(p)),
+        None => Ok(()),
+    }
+}
+
+impl Operation {
+    fn apply(self, password: &mut [u8]) -> Result<(), ScrambleError> {
+        if password.is_empty() {
+            return Ok(());
+        }
+
+        let len = password.len();
+        match self {
+            Self::SwapPosition(x, y) => {
+                check_range(password, &[x, y])?;
+                password.swap(x, y);
+            }
+            Self::SwapLetter(a, b) => {
+                let (x, y) = (position(password, a)?, position(password, b)?);
+                password.swap(x, y);
+            }
+            Self::RotateLeft(n) => password.rotate_left(n % len),
+            Self::RotateRight(n) => password.rotate_right(n % len),
+            Self::RotateOnLetter(letter) => {
+                let index = position(password, letter)?;
+                let steps = 1 + index + usize::from(index >= 4);
+                password.rotate_right(steps % len);
+            }
+            Self::Reverse(x, y) => {
+                check_range(password, &[x, y])?;
+                password[x.min(y)..=x.max(y)].reverse();
+            }
+            Self::Move(x, y) => {
+                check_range(password, &[x, y])?;
+                if x < y {
+                    password[x..=y].rotate_left(1);
+                } else {
+                    password[y..=x].rotate_right(1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn invert(self, password: &mut [u8]) -> Result<(), ScrambleError> {
+        match self {
+            Self::SwapPosition(..) | Self::SwapLetter(..) | Self::Reverse(..) => {
+                self.apply(password)
+            }
+            Self::RotateLeft(n) => Self::RotateRight(n).apply(password),
+            Self::RotateRight(n) => Self::RotateLeft(n).apply(password),
+            Self::Move(x, y) => Self::Move(y, x).apply(password),
+            Self::RotateOnLetter(letter) => {
+                position(password, letter)?;
+
+                let candidates = (0..password.len())
+                    .map(|n| {
+                        let mut candidate = password.to_vec();
+                        candidate.rotate_left(n);
+                        candidate
+                    })
+                    .filter(|candidate| {
+                        let mut scrambled = candidate.clone();
+                        self.apply(&mut scrambled).is_ok() && scrambled == password
+                    })
+                    .unique()
+                    .collect_vec();
+
+                match candidates.as_slice() {
+                    [only] => {
+                        password.copy_from_slice(only);
+                        Ok(())
+                    }
+                    [] => Err(ScrambleError::NoPreimage { letter }),
+                    _ => Err(ScrambleError::Ambiguous {
+                        letter,
+                        candidates: candidates.len(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+fn single_letter(s: &str) -> Result<u8, Box<dyn error::Error>> {
+    match s.as_bytes() {
+        &[c] => Ok(c),
+        _ => Err(format!("Expected a single letter, got {s:?}").into()),
+    }
+}
+
+impl FromStr for Operation {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect_vec();
+
+        Ok(match words.as_slice() {
+            ["swap", "position", x, "with", "position", y] => {
+                Self::SwapPosition(x.parse()?, y.parse()?)
+            }
+            ["swap", "letter", a, "with", "letter", b] => {
+                Self::SwapLetter(single_letter(a)?, single_letter(b)?)
+            }
+            ["rotate", "left", n, "step" | "steps"] => Self::RotateLeft(n.parse()?),
+            ["rotate", "right", n, "step" | "steps"] => Self::RotateRight(n.parse()?),
+            ["rotate", "based", "on", "position", "of", "letter", l] => {
+                Self::RotateOnLetter(single_letter(l)?)
+            }
+            ["reverse", "positions", x, "through", y] => Self::Reverse(x.parse()?, y.parse()?),
+            ["move", "position", x, "to", "position", y] => Self::Move(x.parse()?, y.parse()?),
+            _ => return Err(format!("Unknown operation {s:?}").into()),
+        })
+    }
+}
+
+fn scramble(program: &[Operation], password: &str) -> Result<String, ScrambleError> {
+    let mut password = password.as_bytes().to_vec();
+    for op in program {
+        op.apply(&mut password)?;
+    }
+
+    Ok(String::from_utf8(password).expect("Operations only move bytes around"))
+}
+
+fn unscramble(program: &[Operation], scrambled: &str) -> Result<String, ScrambleError> {
+    let mut password = scrambled.as_bytes().to_vec();
+    for op in program.iter().rev() {
+        op.invert(&mut password)?;
+    }
+
+    Ok(String::from_utf8(password).expect("Operations only move bytes around"))
+}
+
+#[aoc_generator(day21)]
+fn parse(input: &str) -> Result<Vec<Operation>, Box<dyn error::Error>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::parse)
+        .try_collect()
+}
+
+#[aoc(day21, part1)]
+fn part1(input: &[Operation]) -> Result<String, ScrambleError> {
+    scramble(input, PASSWORD)
+}
+
+#[aoc(day21, part2)]
+fn part2(input: &[Operation]) -> Result<String, ScrambleError> {
+    unscramble(input, SCRAMBLED)
+}
+
+solution! {
+    Day21 {
+        day: 21,
+        title: "Scrambled Letters and Hash",
+        input: Vec<Operation> = parse,
+        part1: String = part1,
+        part2: String = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        swap position 4 with position 0
+        swap letter d with letter b
+        reverse positions 0 through 4
+        rotate left 1 step
+        move position 1 to position 4
+        move position 3 to position 0
+        rotate based on position of letter b
+        rotate based on position of letter d
+    ";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            scramble(&parse(EXAMPLE).unwrap(), "abcde").unwrap(),
+            "decab"
+        );
+    }
+
+    #[test]
+    fn example_steps() {
+        let program = parse(EXAMPLE).unwrap();
+        let expected = [
+            "ebcda", "edcba", "abcde", "bcdea", "bdeac", "abdec", "ecabd", "decab",
+        ];
+
+        let mut password = b"abcde".to_vec();
+        for (op, expected) in program.iter().zip(expected) {
+            op.apply(&mut password).unwrap();
+            assert_eq!(password, expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn ambiguous_rotation() {
+        // On five letters, a letter that was at index 2 or 4 both end up at index 0,
+        // and nothing ends up at index 4.
+        let mut password = b"bcdea".to_vec();
+        assert_eq!(
+            Operation::RotateOnLetter(b'b').invert(&mut password),
+            Err(ScrambleError::Ambiguous {
+                letter: b'b',
+                candidates: 2
+            })
+        );
+
+        let mut password = b"cdeab".to_vec();
+        assert_eq!(
+            Operation::RotateOnLetter(b'b').invert(&mut password),
+            Err(ScrambleError::NoPreimage { letter: b'b' })
+        );
+    }
+
+    #[test]
+    fn eight_letters_round_trip() {
+        let program = parse(EXAMPLE).unwrap();
+        for perm in PASSWORD.bytes().permutations(PASSWORD.len()).step_by(97) {
+            let password = String::from_utf8(perm).unwrap();
+            let scrambled = scramble(&program, &password).unwrap();
+            assert_eq!(unscramble(&program, &scrambled).unwrap(), password);
+        }
+    }
+
+    #[test]
+    fn every_operation_inverts() {
+        let ops = [
+            Operation::SwapPosition(1, 6),
+            Operation::SwapLetter(b'a', b'h'),
+            Operation::RotateLeft(11),
+            Operation::RotateRight(3),
+            Operation::RotateOnLetter(b'e'),
+            Operation::Reverse(5, 2),
+            Operation::Move(0, 7),
+            Operation::Move(6, 2),
+        ];
+
+        for op in ops {
+            let mut password = PASSWORD.as_bytes().to_vec();
+            op.apply(&mut password).unwrap();
+            op.invert(&mut password).unwrap();
+            assert_eq!(password, PASSWORD.as_bytes(), "{op:?}");
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::{error, fmt, str::FromStr};
+
+use itertools::Itertools;
+use ndarray::Array2;
+
+use crate::search;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+struct Node {
+    size: u32,
+    used: u32,
+    avail: u32,
+}
+
+// (x, y) as in the node names; the grid itself is indexed [y, x].
+type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Listing {
+    position: Position,
+    node: Node,
+}
+
+fn parse_terabytes(s: &str) -> Result<u32, Box<dyn error::Error>> {
+    Ok(s.strip_suffix('T')
+        .ok_or_else(|| format!("Expected a size in terabytes, got {s:?}"))?
+        .parse()?)
+}
+
+impl FromStr for Listing {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.split_whitespace().collect_vec();
+        let [name, size, used, avail, _] = fields.as_slice() else {
+            return Err(format!("Expected 5 columns in {s:?}").into());
+        };
+
+        let (x, y) = name
+            .strip_prefix("/dev/grid/node-x")
+            .and_then(|rest| rest.split_once("-y"))
+            .ok_or_else(|| format!("Unexpected node name {name:?}"))?;
+
+        Ok(Self {
+            position: (x.parse()?, y.parse()?),
+            node: Node {
+                size: parse_terabytes(size)?,
+                used: parse_terabytes(used)?,
+                avail: parse_terabytes(avail)?,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cluster {
+    nodes: Array2<Node>,
+}
+
+impl Cluster {
+    fn width(&self) -> usize {
+        self.nodes.ncols()
+    }
+
+    fn height(&self) -> usize {
+        self.nodes.nrows()
+    }
+
+    fn node(&self, (x, y): Position) -> Node {
+        self.nodes[[y, x]]
+    }
+
+    fn viable_pairs(&self) -> usize {
+        self.nodes
+            .iter()
+            .enumerate()
+            .cartesian_product(self.nodes.iter().enumerate())
+            .filter(|&((i, a), (j, b))| i != j && a.used != 0 && a.used <= b.avail)
+            .count()
+    }
+
+    fn empty_node(&self) -> Result<Position, String> {
+        let empty = self
+            .nodes
+            .indexed_iter()
+            .filter(|(_, node)| node.used == 0)
+            .map(|((y, x), _)| (x, y))
+            .collect_vec();
+
+        match empty.as_slice() {
+            &[position] => Ok(position),
+            _ => Err(format!(
+                "Expected exactly one empty node, found {}",
+                empty.len()
+            )),
+        }
+    }
+
+    // A node is a wall if its data could never fit in the empty node.
+    fn is_wall(&self, position: Position, empty: Position) -> bool {
+        self.node(position).used > self.node(empty).size
+    }
+
+    fn goal(&self) -> Position {
+        (self.width() - 1, 0)
+    }
+
+    fn adjacent(&self, (x, y): Position) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width(), self.height());
+
+        [
+            x.checked_sub(1).map(|x| (x, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x + 1, y)).filter(|&(x, _)| x < width),
+            Some((x, y + 1)).filter(|&(_, y)| y < height),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    // Breadth-first search over (empty slot, goal data) positions: each step
+    // moves the contents of a neighbour into the empty slot.
+    fn fewest_steps(&self) -> Result<usize, String> {
+        let empty = self.empty_node()?;
+        let start = (empty, self.goal());
+
+        let neighbours = |&(hole, goal): &(Position, Position)| {
+            self.adjacent(hole)
+                .filter(move |&next| !self.is_wall(next, empty))
+                .map(move |next| (next, if next == goal { hole } else { goal }))
+        };
+
+        search::bfs(start, neighbours, |&(_, goal)| goal == (0, 0))
+            .found
+            .map(|found| found.cost)
+            .ok_or_else(|| "The goal data can never reach (0, 0)".to_string())
+    }
+}
+
+// Same layout as the puzzle: `_` is the empty node, `#` nodes whose data can
+// never move, `G` the goal data and (0, 0) is in parentheses.
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let empty = self.empty_node().ok();
+
+        for y in 0..self.height() {
+            let line = (0..self.width())
+                .map(|x| {
+                    let c = if (x, y) == self.goal() {
+                        'G'
+                    } else if Some((x, y)) == empty {
+                        '_'
+                    } else if empty.is_some_and(|empty| self.is_wall((x, y), empty)) {
+                        '#'
+                    } else {
+                        '.'
+                    };
+
+                    if (x, y) == (0, 0) {
+                        format!("({c})")
+                    } else {
+                        format!(" {c} ")
+                    }
+                })
+                .join("");
+
+            if y > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[aoc_generator(day22)]
+fn parse(input: &str) -> Result<Cluster, Box<dyn error::Error>> {
+    let listings: Vec<Listing> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("/dev/grid/"))
+        .map(str::parse)
+        .try_collect()?;
+
+    let width = listings
+        .iter()
+        .map(|l| l.position.0 + 1)
+        .max()
+        .ok_or("No nodes")?;
+    let height = listings
+        .iter()
+        .map(|l| l.position.1 + 1)
+        .max()
+        .ok_or("No nodes")?;
+    if listings.len() != width * height {
+        return Err(format!(
+            "Expected {width}x{height} = {} nodes, got {}",
+            width * height,
+            listings.len()
+        )
+        .into());
+    }
+
+    let mut nodes = Array2::default((height, width));
+    for Listing {
+        position: (x, y),
+        node,
+    } in listings
+    {
+        nodes[[y, x]] = node;
+    }
+
+    Ok(Cluster { nodes })
+}
+
+#[aoc(day22, part1)]
+fn part1(cluster: &Cluster) -> usize {
+    cluster.viable_pairs()
+}
+
+#[aoc(day22, part2)]
+fn part2(cluster: &Cluster) -> Result<usize, String> {
+    cluster.fewest_steps()
+}
+
+solution! {
+    Day22 {
+        day: 22,
+        title: "Grid Computing",
+        input: Cluster = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        root@ebhq-gridcenter# df -h
+        Filesystem            Size  Used  Avail  Use%
+        /dev/grid/node-x0-y0   10T    8T     2T   80%
+        /dev/grid/node-x0-y1   11T    6T     5T   54%
+        /dev/grid/node-x0-y2   32T   28T     4T   87%
+        /dev/grid/node-x1-y0    9T    7T     2T   77%
+        /dev/grid/node-x1-y1    8T    0T     8T    0%
+        /dev/grid/node-x1-y2   11T    7T     4T   63%
+        /dev/grid/node-x2-y0   10T    6T     4T   60%
+        /dev/grid/node-x2-y1    9T    8T     1T   88%
+        /dev/grid/node-x2-y2    9T    6T     3T   66%
+    ";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(7));
+    }
+
+    #[test]
+    fn render_example() {
+        assert_eq!(
+            parse(EXAMPLE).unwrap().to_string(),
+            "(.) .  G\n .  _  .\n #  .  ."
+        );
+    }
+}
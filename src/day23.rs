@@ -0,0 +1,66 @@
+use crate::assembunny::{Machine, ParseError, Program};
+
+fn run_with_eggs(program: &Program, eggs: i64) -> i64 {
+    let mut machine = Machine::new(&program.instructions, true);
+    machine.set_register(0, eggs);
+    machine.run();
+    machine.register(0)
+}
+
+#[aoc_generator(day23)]
+fn parse(input: &str) -> Result<Program, ParseError> {
+    input.parse()
+}
+
+#[aoc(day23, part1)]
+fn part1(input: &Program) -> i64 {
+    run_with_eggs(input, 7)
+}
+
+#[aoc(day23, part2)]
+fn part2(input: &Program) -> i64 {
+    run_with_eggs(input, 12)
+}
+
+solution! {
+    Day23 {
+        day: 23,
+        title: "Safe Cracking",
+        input: Program = parse,
+        part1: i64 = part1,
+        part2: i64 = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assembunny::FACTORIAL;
+
+    const EXAMPLE: &str = "
+        cpy 2 a
+        tgl a
+        tgl a
+        tgl a
+        cpy 1 a
+        dec a
+        dec a
+    ";
+
+    #[test]
+    fn part1_example() {
+        let program = parse(EXAMPLE).unwrap();
+        assert_eq!(run_with_eggs(&program, 0), 3);
+
+        let mut machine = Machine::new(&program.instructions, false);
+        machine.run();
+        assert_eq!(machine.register(0), 3);
+    }
+
+    #[test]
+    fn factorial_program() {
+        let program = parse(FACTORIAL).unwrap();
+        assert_eq!(part1(&program), 5040 + 42);
+        assert_eq!(part2(&program), 479001600 + 42);
+    }
+}
@@ -0,0 +1,289 @@
+use std::{error, str::FromStr};
+
+use fnv::FnvHashMap;
+use itertools::Itertools;
+use ndarray::Array2;
+
+use crate::search;
+
+/// (row, column)
+pub type Position = (usize, usize);
+
+/// The duct map: which cells are open and where each numbered point is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ducts {
+    open: Array2<bool>,
+    points: Vec<Position>,
+}
+
+impl Ducts {
+    fn adjacent(&self, (row, col): Position) -> impl Iterator<Item = Position> + '_ {
+        [
+            row.checked_sub(1).map(|r| (r, col)),
+            Some((row + 1, col)),
+            col.checked_sub(1).map(|c| (row, c)),
+            Some((row, col + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&pos| self.open.get(pos).copied().unwrap_or(false))
+    }
+
+    /// Points of interest, indexed by their number.
+    pub fn points(&self) -> &[Position] {
+        &self.points
+    }
+
+    fn distances_from(&self, start: Position) -> FnvHashMap<Position, usize> {
+        search::reachable_within(start, |&pos| self.adjacent(pos), usize::MAX)
+    }
+
+    /// Steps between every pair of points, `[[from, to]]`.
+    pub fn distance_matrix(&self) -> Result<Array2<usize>, String> {
+        let n = self.points.len();
+        let mut matrix = Array2::zeros((n, n));
+
+        for (i, &from) in self.points.iter().enumerate() {
+            let dist = self.distances_from(from);
+            for (j, &to) in self.points.iter().enumerate() {
+                matrix[[i, j]] = *dist
+                    .get(&to)
+                    .ok_or_else(|| format!("Cannot reach {j} from {i}"))?;
+            }
+        }
+
+        Ok(matrix)
+    }
+}
+
+/// The points in visiting order, starting at 0 and ending back at 0 for a
+/// round trip, with the total number of steps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Tour {
+    pub length: usize,
+    pub order: Vec<usize>,
+}
+
+// Held-Karp: `best[mask][last]` is the shortest walk that starts at 0, visits
+// exactly the points in `mask` and ends at `last`.
+pub fn shortest_tour(dist: &Array2<usize>, return_home: bool) -> Tour {
+    let n = dist.nrows();
+    if n <= 1 {
+        return Tour {
+            length: 0,
+            order: (0..n)
+                .chain(if return_home { 0..n } else { 0..0 })
+                .collect(),
+        };
+    }
+
+    let full = (1 << n) - 1;
+    let mut best = Array2::from_elem((1 << n, n), usize::MAX);
+    let mut parent = Array2::from_elem((1 << n, n), usize::MAX);
+    best[[1, 0]] = 0;
+
+    for mask in (1..=full).filter(|mask| mask & 1 == 1) {
+        for last in (0..n).filter(|last| mask & (1 << last) != 0) {
+            let cost = best[[mask, last]];
+            if cost == usize::MAX {
+                continue;
+            }
+
+            for next in (0..n).filter(|next| mask & (1 << next) == 0) {
+                let next_mask = mask | (1 << next);
+                let next_cost = cost + dist[[last, next]];
+                if next_cost < best[[next_mask, next]] {
+                    best[[next_mask, next]] = next_cost;
+                    parent[[next_mask, next]] = last;
+                }
+            }
+        }
+    }
+
+    let home = |last: usize| if return_home { dist[[last, 0]] } else { 0 };
+    let (mut last, length) = (1..n)
+        .map(|last| (last, best[[full, last]] + home(last)))
+        .min_by_key(|&(_, length)| length)
+        .expect("At least two points");
+
+    let mut order = vec![];
+    let mut mask = full;
+    while last != usize::MAX {
+        order.push(last);
+        let prev = parent[[mask, last]];
+        mask &= !(1 << last);
+        last = prev;
+    }
+    order.reverse();
+
+    if return_home {
+        order.push(0);
+    }
+
+    Tour { length, order }
+}
+
+impl FromStr for Ducts {
+    type Err = Box<dyn error::Error>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect_vec();
+
+        let width = lines.first().ok_or("Empty map")?.len();
+        let mut open = Array2::from_elem((lines.len(), width), false);
+        let mut points = vec![];
+
+        for (row, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(format!("Line {row} is {} wide, expected {width}", line.len()).into());
+            }
+
+            for (col, c) in line.bytes().enumerate() {
+                match c {
+                    b'#' => (),
+                    b'.' => open[[row, col]] = true,
+                    b'0'..=b'9' => {
+                        open[[row, col]] = true;
+                        points.push(((c - b'0') as usize, (row, col)));
+                    }
+                    _ => return Err(format!("Unexpected {:?} in map", c as char).into()),
+                }
+            }
+        }
+
+        points.sort_unstable();
+        if points.iter().enumerate().any(|(i, &(n, _))| i != n) {
+            return Err("Points of interest must be numbered 0 to n without gaps".into());
+        }
+
+        Ok(Self {
+            open,
+            points: points.into_iter().map(|(_, pos)| pos).collect(),
+        })
+    }
+}
+
+#[aoc_generator(day24)]
+fn parse(input: &str) -> Result<Ducts, Box<dyn error::Error>> {
+    input.parse()
+}
+
+#[aoc(day24, part1)]
+fn part1(ducts: &Ducts) -> Result<usize, String> {
+    Ok(shortest_tour(&ducts.distance_matrix()?, false).length)
+}
+
+#[aoc(day24, part2)]
+fn part2(ducts: &Ducts) -> Result<usize, String> {
+    Ok(shortest_tour(&ducts.distance_matrix()?, true).length)
+}
+
+solution! {
+    Day24 {
+        day: 24,
+        title: "Air Duct Spelunking",
+        input: Ducts = parse,
+        part1: usize = part1,
+        part2: usize = part2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        ###########
+        #0.1.....2#
+        #.#######.#
+        #4.......3#
+        ###########
+    ";
+
+    fn brute_force(dist: &Array2<usize>, return_home: bool) -> usize {
+        let n = dist.nrows();
+        (1..n)
+            .permutations(n - 1)
+            .map(|perm| {
+                let walk: usize = std::iter::once(0)
+                    .chain(perm.iter().copied())
+                    .tuple_windows()
+                    .map(|(a, b)| dist[[a, b]])
+                    .sum();
+                walk + if return_home {
+                    dist[[perm[n - 2], 0]]
+                } else {
+                    0
+                }
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn walk_length(dist: &Array2<usize>, order: &[usize]) -> usize {
+        order
+            .iter()
+            .tuple_windows()
+            .map(|(&a, &b)| dist[[a, b]])
+            .sum()
+    }
+
+    #[test]
+    fn distance_matrix_example() {
+        let dist = parse(EXAMPLE).unwrap().distance_matrix().unwrap();
+        assert_eq!(dist[[0, 4]], 2);
+        assert_eq!(dist[[4, 1]], 4);
+        assert_eq!(dist[[1, 2]], 6);
+        assert_eq!(dist[[2, 3]], 2);
+        assert_eq!(parse(EXAMPLE).unwrap().points()[3], (3, 9));
+    }
+
+    #[test]
+    fn part1_example() {
+        let ducts = parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&ducts), Ok(14));
+
+        let tour = shortest_tour(&ducts.distance_matrix().unwrap(), false);
+        assert_eq!(tour.order, vec![0, 4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn part2_example() {
+        let ducts = parse(EXAMPLE).unwrap();
+        let dist = ducts.distance_matrix().unwrap();
+        let tour = shortest_tour(&dist, true);
+
+        assert_eq!(part2(&ducts), Ok(brute_force(&dist, true)));
+        assert_eq!(tour.order.first(), Some(&0));
+        assert_eq!(tour.order.last(), Some(&0));
+        assert_eq!(walk_length(&dist, &tour.order), tour.length);
+    }
+
+    #[test]
+    fn held_karp_matches_brute_force() {
+        for n in 2..=7 {
+            // Deterministic asymmetric distances from a small LCG.
+            let mut seed = 12345_u64 + n as u64;
+            let dist = Array2::from_shape_fn((n, n), |(i, j)| {
+                if i == j {
+                    return 0;
+                }
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (seed >> 33) as usize % 50 + 1
+            });
+
+            for return_home in [false, true] {
+                let tour = shortest_tour(&dist, return_home);
+                assert_eq!(tour.length, brute_force(&dist, return_home));
+                assert_eq!(walk_length(&dist, &tour.order), tour.length);
+                assert_eq!(tour.order.iter().unique().count(), n);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::{error, fmt};
+
+use fnv::FnvHashMap;
+
+use crate::assembunny::{Instruction, Machine, ParseError, Program};
+
+// A candidate that runs this long without printing anything, or prints this
+// much without repeating a state, is reported rather than skipped.
+const MAX_STEPS_PER_OUTPUT: usize = 1_000_000;
+const MAX_OUTPUTS: usize = 100_000;
+
+/// The lowest `a` that makes the program print 0, 1, 0, 1, ... forever, and
+/// how many outputs it takes for the machine to come back to the same state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClockSignal {
+    pub a: i64,
+    pub period: usize,
+}
+
+/// The search could not decide whether `a` produces a clock signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClockError {
+    Stalled { a: i64, outputs: usize },
+    NoRepeat { a: i64 },
+    Exhausted,
+}
+
+impl fmt::Display for ClockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stalled { a, outputs } => write!(
+                f,
+                "a = {a}: no output within {MAX_STEPS_PER_OUTPUT} steps after {outputs} outputs"
+            ),
+            Self::NoRepeat { a } => {
+                write!(f, "a = {a}: no repeated state within {MAX_OUTPUTS} outputs")
+            }
+            Self::Exhausted => write!(f, "No initial value produces a clock signal"),
+        }
+    }
+}
+
+impl error::Error for ClockError {}
+
+// `Ok(None)` once the machine halts.
+fn next_output(machine: &mut Machine) -> Result<Option<i64>, ()> {
+    for _ in 0..MAX_STEPS_PER_OUTPUT {
+        if machine.is_halted() {
+            return Ok(None);
+        }
+
+        if let Some(output) = machine.step() {
+            return Ok(Some(output));
+        }
+    }
+
+    Err(())
+}
+
+/// Runs the program until the machine is provably stuck in a 0, 1, 0, 1, ...
+/// loop, i.e. it comes back to the same state after printing the same bit.
+/// Returns the number of outputs in one loop, or `None` if the signal breaks
+/// the pattern or the program halts.
+pub fn clock_period(program: &[Instruction], a: i64) -> Result<Option<usize>, ClockError> {
+    let mut machine = Machine::new(program, true);
+    machine.set_register(0, a);
+
+    let mut seen = FnvHashMap::default();
+
+    for outputs in 0..MAX_OUTPUTS {
+        let expected = (outputs % 2) as i64;
+        let key = (
+            machine.pc(),
+            machine.registers(),
+            machine.program().to_vec(),
+            expected,
+        );
+
+        if let Some(previous) = seen.insert(key, outputs) {
+            return Ok(Some(outputs - previous));
+        }
+
+        match next_output(&mut machine) {
+            Ok(Some(output)) if output == expected => (),
+            Ok(_) => return Ok(None),
+            Err(()) => return Err(ClockError::Stalled { a, outputs }),
+        }
+    }
+
+    Err(ClockError::NoRepeat { a })
+}
+
+pub fn find_clock(program: &[Instruction]) -> Result<ClockSignal, ClockError> {
+    for a in 1..=i64::MAX {
+        if let Some(period) = clock_period(program, a)? {
+            return Ok(ClockSignal { a, period });
+        }
+    }
+
+    Err(ClockError::Exhausted)
+}
+
+#[aoc_generator(day25)]
+fn parse(input: &str) -> Result<Program, ParseError> {
+    input.parse()
+}
+
+#[aoc(day25, part1)]
+fn part1(input: &Program) -> Result<i64, ClockError> {
+    find_clock(&input.instructions).map(|clock| clock.a)
+}
+
+// The last day has no second puzzle, only the free star for finishing.
+solution! {
+    Day25 {
+        day: 25,
+        title: "Clock Signal",
+        input: Program = parse,
+        part1: i64 = part1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Prints the bits of a + 2 * 3 from least significant, forever, the same
+    // way the puzzle inputs do with a larger constant.
+    const CLOCK: &str = "
+        cpy a d
+        cpy 3 c
+        cpy 2 b
+        inc d
+        dec b
+        jnz b -2
+        dec c
+        jnz c -5
+        cpy d a
+        jnz 0 0
+        cpy a b
+        cpy 0 a
+        cpy 2 c
+        jnz b 2
+        jnz 1 6
+        dec b
+        dec c
+        jnz c -4
+        inc a
+        jnz 1 -7
+        cpy 2 b
+        jnz c 2
+        jnz 1 4
+        dec b
+        dec c
+        jnz 1 -4
+        jnz 0 0
+        out b
+        jnz a -19
+        jnz 1 -21
+    ";
+
+    #[test]
+    fn finds_lowest_clock() {
+        let program = parse(CLOCK).unwrap().instructions;
+        // 4 + 6 = 0b1010 prints 0, 1, 0, 1 and then starts over.
+        assert_eq!(find_clock(&program), Ok(ClockSignal { a: 4, period: 4 }));
+    }
+
+    #[test]
+    fn rejects_other_signals() {
+        let program = parse(CLOCK).unwrap().instructions;
+        for a in 1..4 {
+            assert_eq!(clock_period(&program, a), Ok(None), "a = {a}");
+        }
+        assert_eq!(clock_period(&program, 4 + 16), Ok(None));
+        assert_eq!(clock_period(&program, 36), Ok(Some(6)));
+    }
+
+    #[test]
+    fn halting_program_is_not_a_clock() {
+        let program = parse("out 0\nout 1").unwrap().instructions;
+        assert_eq!(clock_period(&program, 1), Ok(None));
+    }
+
+    #[test]
+    fn silent_program_is_an_error() {
+        let program = parse("out 0\nout 1\njnz 1 0").unwrap().instructions;
+        assert_eq!(
+            clock_period(&program, 1),
+            Err(ClockError::Stalled { a: 1, outputs: 2 })
+        );
+        assert!(find_clock(&program).is_err());
+    }
+
+    #[test]
+    fn endless_alternation() {
+        let program = parse("out 0\nout 1\njnz 1 -2").unwrap().instructions;
+        assert_eq!(clock_period(&program, 1), Ok(Some(2)));
+    }
+}
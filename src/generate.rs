@@ -0,0 +1,504 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// SplitMix64: small, seedable and the same on every platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, for `n > 0`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Uniform in `from..to`.
+    pub fn range(&mut self, from: u64, to: u64) -> u64 {
+        from + self.below(to - from)
+    }
+
+    pub fn chance(&mut self, one_in: u64) -> bool {
+        self.below(one_in) == 0
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+
+    pub fn letters(&mut self, alphabet: &[u8], len: usize) -> String {
+        (0..len).map(|_| self.pick(alphabet) as char).collect()
+    }
+}
+
+/// A generated input together with the answers the solvers must give for it,
+/// formatted the way they print them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    pub input: String,
+    pub part1: String,
+    pub part2: String,
+}
+
+/// A square spiral turning the same way every time, with sides `s, s, 2s, 2s,
+/// 3s, ...` so it never crosses itself, then a walk back inwards through the
+/// previous ring.
+pub fn day01(seed: u64) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let turn = rng.pick(&['L', 'R']);
+    let spacing = rng.range(1, 6) as i64;
+    let rings = rng.range(100, 150) as i64;
+
+    // (row, column) steps for up, right, down, left.
+    const HEADINGS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let rotate = |heading: usize| {
+        if turn == 'R' {
+            (heading + 1) % 4
+        } else {
+            (heading + 3) % 4
+        }
+    };
+
+    let mut instructions = vec![];
+    let (mut heading, mut position) = (0, (0, 0));
+    for side in 0..2 * rings {
+        let distance = (side / 2 + 1) * spacing;
+        heading = rotate(heading);
+        position.0 += HEADINGS[heading].0 * distance;
+        position.1 += HEADINGS[heading].1 * distance;
+        instructions.push(format!("{turn}{distance}"));
+    }
+
+    // Carry on along the outside for one spacing, then cut back in: the
+    // previous ring is exactly one spacing away.
+    heading = rotate(heading);
+    position.0 += HEADINGS[heading].0 * spacing;
+    position.1 += HEADINGS[heading].1 * spacing;
+    instructions.push(format!("{turn}{spacing}"));
+
+    heading = rotate(heading);
+    let (dr, dc) = HEADINGS[heading];
+    let last = rng.range(spacing as u64, (rings * spacing) as u64) as i64;
+    instructions.push(format!("{turn}{last}"));
+
+    let manhattan = |steps: i64| (position.0 + dr * steps).abs() + (position.1 + dc * steps).abs();
+
+    Puzzle {
+        input: instructions.join(", "),
+        part1: manhattan(last).to_string(),
+        part2: manhattan(spacing).to_string(),
+    }
+}
+
+// Walks `keypad` from the key in row `start` and returns where it stops.
+fn press(keypad: &[&[u8]], start: (usize, usize), moves: &[u8]) -> (usize, usize) {
+    let key = |(row, col): (usize, usize)| {
+        keypad
+            .get(row)
+            .and_then(|line| line.get(col))
+            .filter(|&&key| key != b' ')
+    };
+
+    moves.iter().fold(start, |(row, col), step| {
+        let next = match step {
+            b'U' => (row.wrapping_sub(1), col),
+            b'D' => (row + 1, col),
+            b'L' => (row, col.wrapping_sub(1)),
+            _ => (row, col + 1),
+        };
+        if key(next).is_some() {
+            next
+        } else {
+            (row, col)
+        }
+    })
+}
+
+pub fn day02(seed: u64) -> Puzzle {
+    const SQUARE: [&[u8]; 3] = [b"123", b"456", b"789"];
+    const DIAMOND: [&[u8]; 5] = [b"  1  ", b" 234 ", b"56789", b" ABC ", b"  D  "];
+
+    let mut rng = Rng::new(seed);
+    let lines = (0..5)
+        .map(|_| {
+            let len = rng.range(300, 600) as usize;
+            rng.letters(b"UDLR", len)
+        })
+        .collect_vec();
+
+    let code = |keypad: &[&[u8]], start| {
+        let mut at = start;
+        lines
+            .iter()
+            .map(|line| {
+                at = press(keypad, at, line.as_bytes());
+                keypad[at.0][at.1] as char
+            })
+            .collect()
+    };
+
+    Puzzle {
+        input: lines.iter().map(|line| format!("{line}\n")).collect(),
+        part1: code(&SQUARE, (1, 1)),
+        part2: code(&DIAMOND, (2, 0)),
+    }
+}
+
+fn is_triangle([a, b, c]: [u64; 3]) -> bool {
+    a + b > c && b + c > a && c + a > b
+}
+
+/// Every column of every block of three rows is a triangle picked to be valid
+/// or not; the rows are whatever that leaves.
+pub fn day03(seed: u64) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let mut rows = vec![];
+    let mut valid_columns = 0;
+
+    for _ in 0..rng.range(500, 700) {
+        let columns = [(); 3].map(|_| {
+            let (a, b) = (rng.range(1, 500), rng.range(1, 500));
+            let valid = rng.chance(2);
+            let c = if valid {
+                rng.range(a.abs_diff(b) + 1, a + b)
+            } else {
+                rng.range(a + b, 1000)
+            };
+            valid_columns += valid as usize;
+
+            let mut sides = [a, b, c];
+            rng.shuffle(&mut sides);
+            sides
+        });
+        rows.extend((0..3).map(|row| columns.map(|column| column[row])));
+    }
+
+    Puzzle {
+        input: rows
+            .iter()
+            .map(|[a, b, c]| format!("  {a:3}  {b:3}  {c:3}\n"))
+            .collect(),
+        part1: rows
+            .iter()
+            .filter(|&&row| is_triangle(row))
+            .count()
+            .to_string(),
+        part2: valid_columns.to_string(),
+    }
+}
+
+fn shift(letter: u8, by: u64) -> u8 {
+    b'a' + (((letter - b'a') as u64 + by) % 26) as u8
+}
+
+// The five most common letters, ties broken alphabetically.
+fn checksum(name: &str) -> String {
+    name.bytes()
+        .filter(u8::is_ascii_lowercase)
+        .counts()
+        .into_iter()
+        .sorted_by_key(|&(letter, count)| (std::cmp::Reverse(count), letter))
+        .take(5)
+        .map(|(letter, _)| letter as char)
+        .collect()
+}
+
+/// Rooms with at least five distinct letters, about half of them with a
+/// decoy checksum made by swapping two letters of the real one. The North Pole
+/// object storage is always among the real rooms.
+pub fn day04(seed: u64) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let count = rng.range(900, 1100);
+    let north_pole = rng.below(count);
+
+    let mut input = String::new();
+    let (mut sector_sum, mut north_pole_sector) = (0, 0);
+
+    for i in 0..count {
+        let sector = rng.range(100, 1000);
+        let encrypt = |words: &[String]| {
+            words
+                .iter()
+                .map(|word| {
+                    word.bytes()
+                        .map(|b| shift(b, 26 - sector % 26) as char)
+                        .collect::<String>()
+                })
+                .join("-")
+        };
+
+        let (name, mut sum) = loop {
+            let words = if i == north_pole {
+                vec!["northpole".to_string(), "object".into(), "storage".into()]
+            } else {
+                (0..rng.range(2, 6))
+                    .map(|_| {
+                        let len = rng.range(3, 10) as usize;
+                        rng.letters(LETTERS, len)
+                    })
+                    .collect()
+            };
+            let name = encrypt(&words);
+            let sum = checksum(&name);
+            if sum.len() == 5 {
+                break (name, sum);
+            }
+        };
+
+        let real = i == north_pole || rng.chance(2);
+        if i == north_pole {
+            north_pole_sector = sector;
+        }
+
+        if real {
+            sector_sum += sector;
+        } else {
+            let mut letters = sum.into_bytes();
+            let first = rng.below(5) as usize;
+            let second = (first + rng.range(1, 5) as usize) % 5;
+            letters.swap(first, second);
+            sum = String::from_utf8(letters).expect("Checksum is ASCII");
+        }
+
+        writeln!(input, "{name}-{sector}[{sum}]").expect("Write to string failed");
+    }
+
+    Puzzle {
+        input,
+        part1: sector_sum.to_string(),
+        part2: north_pole_sector.to_string(),
+    }
+}
+
+/// In every column each letter appears `k` times, except the message letter
+/// (`k + 2` times) and the modified message letter (`k - 1` times).
+pub fn day06(seed: u64) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let width = rng.range(6, 10) as usize;
+    let k = rng.range(18, 24) as usize;
+
+    let (mut most, mut least) = (String::new(), String::new());
+    let columns = (0..width)
+        .map(|_| {
+            let mut letters = LETTERS.to_vec();
+            rng.shuffle(&mut letters);
+            let (common, rare) = (letters[0], letters[1]);
+            most.push(common as char);
+            least.push(rare as char);
+
+            let mut column = letters
+                .iter()
+                .flat_map(|&l| {
+                    let times = if l == common {
+                        k + 2
+                    } else if l == rare {
+                        k - 1
+                    } else {
+                        k
+                    };
+                    std::iter::repeat_n(l, times)
+                })
+                .collect_vec();
+            rng.shuffle(&mut column);
+            column
+        })
+        .collect_vec();
+
+    let input = (0..columns[0].len())
+        .map(|row| {
+            let line: String = columns.iter().map(|column| column[row] as char).collect();
+            line + "\n"
+        })
+        .collect();
+
+    Puzzle {
+        input,
+        part1: most,
+        part2: least,
+    }
+}
+
+// A sequence with `pattern` somewhere in it and no other ABBA or ABA: free
+// letters never repeat either of the two letters before or after them.
+fn sequence(rng: &mut Rng, pattern: &[u8]) -> String {
+    let free = rng.range(4, 10) as usize;
+    let at = rng.range(0, free as u64 + 1) as usize;
+
+    let mut slots = vec![None; free];
+    slots.splice(at..at, pattern.iter().copied().map(Some));
+
+    for i in 0..slots.len() {
+        if slots[i].is_some() {
+            continue;
+        }
+
+        let nearby = [i.wrapping_sub(2), i.wrapping_sub(1), i + 1, i + 2]
+            .into_iter()
+            .filter_map(|j| slots.get(j).copied().flatten())
+            .collect_vec();
+        let letter = loop {
+            let letter = rng.pick(LETTERS);
+            if !nearby.contains(&letter) {
+                break letter;
+            }
+        };
+        slots[i] = Some(letter);
+    }
+
+    slots
+        .into_iter()
+        .map(|slot| slot.expect("Filled above") as char)
+        .collect()
+}
+
+fn distinct_pair(rng: &mut Rng) -> (u8, u8) {
+    let a = rng.pick(LETTERS);
+    loop {
+        let b = rng.pick(LETTERS);
+        if b != a {
+            return (a, b);
+        }
+    }
+}
+
+/// Addresses whose TLS and SSL support are picked independently, with ABBAs
+/// and ABAs only where they were put on purpose.
+pub fn day07(seed: u64) -> Puzzle {
+    let mut rng = Rng::new(seed);
+    let (mut tls, mut ssl) = (0, 0);
+
+    let input = (0..rng.range(1800, 2200))
+        .map(|_| {
+            let supernets = rng.range(3, 5) as usize;
+            let mut supernet = vec![vec![]; supernets];
+            let mut hypernet = vec![vec![]; supernets - 1];
+
+            // At most one pattern per sequence, so they cannot interact.
+            let mut super_slots = (0..supernets).collect_vec();
+            let mut hyper_slots = (0..supernets - 1).collect_vec();
+            rng.shuffle(&mut super_slots);
+            rng.shuffle(&mut hyper_slots);
+
+            let (a, b) = distinct_pair(&mut rng);
+            match rng.below(4) {
+                0 | 1 => {
+                    supernet[super_slots[0]] = vec![a, b, b, a];
+                    tls += 1;
+                }
+                2 => {
+                    supernet[super_slots[0]] = vec![a, b, b, a];
+                    hypernet[hyper_slots[0]] = vec![b, a, a, b];
+                }
+                _ => hypernet[hyper_slots[0]] = vec![a, b, b, a],
+            }
+
+            let (a, b) = distinct_pair(&mut rng);
+            match rng.below(3) {
+                0 => {
+                    supernet[super_slots[1]] = vec![a, b, a];
+                    hypernet[hyper_slots[1]] = vec![b, a, b];
+                    ssl += 1;
+                }
+                // The mirror image is not enough.
+                1 => {
+                    supernet[super_slots[1]] = vec![a, b, a];
+                    hypernet[hyper_slots[1]] = vec![a, b, a];
+                }
+                _ => (),
+            }
+
+            let mut line = String::new();
+            for (i, pattern) in supernet.iter().enumerate() {
+                if i > 0 {
+                    write!(line, "[{}]", sequence(&mut rng, &hypernet[i - 1]))
+                        .expect("Write to string failed");
+                }
+                line.push_str(&sequence(&mut rng, pattern));
+            }
+            line + "\n"
+        })
+        .collect();
+
+    Puzzle {
+        input,
+        part1: tls.to_string(),
+        part2: ssl.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    fn check(day: u32, generate: fn(u64) -> Puzzle) {
+        for seed in 0..5 {
+            let puzzle = generate(seed);
+            assert_eq!(generate(seed), puzzle, "day {day} is not deterministic");
+
+            for (part, expected) in [(1, &puzzle.part1), (2, &puzzle.part2)] {
+                let run = runner::run(day, part, &puzzle.input).unwrap();
+                assert_eq!(
+                    run.answer.to_string(),
+                    *expected,
+                    "day {day} part {part}, seed {seed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn day01_answers() {
+        check(1, day01);
+    }
+
+    #[test]
+    fn day02_answers() {
+        check(2, day02);
+    }
+
+    #[test]
+    fn day03_answers() {
+        check(3, day03);
+    }
+
+    #[test]
+    fn day04_answers() {
+        check(4, day04);
+    }
+
+    #[test]
+    fn day06_answers() {
+        check(6, day06);
+    }
+
+    #[test]
+    fn day07_answers() {
+        check(7, day07);
+    }
+
+    #[test]
+    fn seeds_differ() {
+        assert_ne!(day07(1).input, day07(2).input);
+        assert_ne!(day01(1), day01(2));
+    }
+}
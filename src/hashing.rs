@@ -0,0 +1,92 @@
+use md5::{Context, Digest};
+
+/// MD5 of a fixed salt followed by a varying suffix.
+///
+/// The salt is fed to the hasher once and the resulting context is cloned for
+/// every suffix, so no intermediate string is ever formatted.
+#[derive(Clone)]
+pub struct SaltedMd5 {
+    prefix: Context,
+}
+
+impl SaltedMd5 {
+    pub fn new<T: AsRef<[u8]>>(salt: T) -> Self {
+        let mut prefix = Context::new();
+        prefix.consume(salt);
+        Self { prefix }
+    }
+
+    pub fn digest<T: AsRef<[u8]>>(&self, suffix: T) -> Digest {
+        let mut ctx = self.prefix.clone();
+        ctx.consume(suffix);
+        ctx.finalize()
+    }
+
+    pub fn digest_index(&self, index: u64) -> Digest {
+        let mut buf = [0; 20];
+        let mut start = buf.len();
+        let mut n = index;
+
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+
+        self.digest(&buf[start..])
+    }
+}
+
+pub fn starts_with_zero_nibbles(digest: &Digest, count: usize) -> bool {
+    (0..count).all(|i| nibble(digest, i) == 0)
+}
+
+pub fn nibble(digest: &Digest, index: usize) -> u8 {
+    let byte = digest.0[index / 2];
+    if index.is_multiple_of(2) {
+        byte >> 4
+    } else {
+        byte & 0x0F
+    }
+}
+
+pub fn to_hex(digest: &Digest) -> [u8; 32] {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut out = [0; 32];
+    for (i, &b) in digest.0.iter().enumerate() {
+        out[2 * i] = HEX[(b >> 4) as usize];
+        out[2 * i + 1] = HEX[(b & 0x0F) as usize];
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn salted_matches_plain_md5() {
+        let hasher = SaltedMd5::new("abc");
+        assert_eq!(hasher.digest_index(0), md5::compute("abc0"));
+        assert_eq!(hasher.digest_index(3231929), md5::compute("abc3231929"));
+        assert_eq!(hasher.digest("DUR"), md5::compute("abcDUR"));
+    }
+
+    #[test]
+    fn hex_matches_format() {
+        let digest = md5::compute("abc18");
+        assert_eq!(to_hex(&digest).as_slice(), format!("{digest:x}").as_bytes());
+    }
+
+    #[test]
+    fn zero_nibbles() {
+        let digest = md5::compute("abc3231929");
+        assert!(starts_with_zero_nibbles(&digest, 5));
+        assert_eq!(nibble(&digest, 5), 1);
+    }
+}
@@ -0,0 +1,247 @@
+use std::{fmt, ops::RangeInclusive};
+
+/// Integer types that an [`IntervalSet`] can be built over.
+pub trait Discrete: Copy + Ord + fmt::Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+
+    /// Number of values in `start..=end`, which must not be inverted.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, i8, i16, i32, i64, usize, isize);
+
+/// A set of integers stored as sorted, disjoint inclusive ranges.
+///
+/// Overlapping and adjacent ranges are merged on insertion, so two sets
+/// holding the same values always have the same ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Adds every value of `range`. Inverted (empty) ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // First stored range that ends at or after `start - 1`, i.e. could
+        // touch the new one.
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.succ().is_some_and(|after| after < start));
+        let mut last = first;
+
+        while let Some(&(s, e)) = self.ranges.get(last) {
+            if end.succ().is_some_and(|after| after < s) {
+                break;
+            }
+
+            start = start.min(s);
+            end = end.max(e);
+            last += 1;
+        }
+
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < value);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| T::count(s, e)).sum()
+    }
+
+    /// Values of `bound` that are not in the set.
+    pub fn complement(&self, bound: RangeInclusive<T>) -> Self {
+        let (low, high) = bound.into_inner();
+        let mut out = Self::new();
+        if low > high {
+            return out;
+        }
+
+        let mut next = Some(low);
+        for &(s, e) in &self.ranges {
+            let Some(from) = next else {
+                break;
+            };
+
+            if e < from {
+                continue;
+            }
+            if s > high {
+                break;
+            }
+
+            if let Some(to) = s.pred().filter(|&to| to >= from) {
+                out.ranges.push((from, to));
+            }
+            next = e.succ();
+        }
+
+        if let Some(from) = next.filter(|&from| from <= high) {
+            out.ranges.push((from, high));
+        }
+
+        out
+    }
+
+    /// Smallest value of `bound` that is not in the set.
+    pub fn first_gap(&self, bound: RangeInclusive<T>) -> Option<T> {
+        self.complement(bound).ranges.first().map(|&(s, _)| s)
+    }
+
+    /// Number of values of `bound` that are not in the set.
+    pub fn uncovered_count(&self, bound: RangeInclusive<T>) -> u128 {
+        self.complement(bound).len()
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const BOUND: u8 = 63;
+
+    fn bitmap(ranges: &[(u8, u8)]) -> [bool; BOUND as usize + 1] {
+        let mut map = [false; BOUND as usize + 1];
+        for &(s, e) in ranges {
+            for v in s..=e {
+                map[v as usize] = true;
+            }
+        }
+        map
+    }
+
+    fn ranges_strategy() -> impl Strategy<Value = Vec<(u8, u8)>> {
+        prop::collection::vec((0..=BOUND, 0..=BOUND), 0..12)
+            .prop_map(|v| v.into_iter().map(|(a, b)| (a.min(b), a.max(b))).collect())
+    }
+
+    #[test]
+    fn merges_overlapping_and_adjacent() {
+        let set: IntervalSet<u32> = [5..=8, 0..=2, 4..=7, 3..=3, 10..=12].into_iter().collect();
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![0..=8, 10..=12]);
+    }
+
+    #[test]
+    fn full_range_edges() {
+        let set: IntervalSet<u32> = [0..=10, u32::MAX - 5..=u32::MAX].into_iter().collect();
+        assert_eq!(set.first_gap(0..=u32::MAX), Some(11));
+        assert_eq!(set.uncovered_count(0..=u32::MAX), (1 << 32) - 17);
+
+        let full: IntervalSet<u32> = [0..=u32::MAX].into_iter().collect();
+        assert_eq!(full.first_gap(0..=u32::MAX), None);
+        assert_eq!(full.len(), 1 << 32);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_bitmap(ranges in ranges_strategy(), probe in 0..=BOUND) {
+            let set: IntervalSet<u8> = ranges.iter().map(|&(s, e)| s..=e).collect();
+            let map = bitmap(&ranges);
+
+            prop_assert_eq!(set.contains(probe), map[probe as usize]);
+            prop_assert_eq!(set.len(), map.iter().filter(|&&b| b).count() as u128);
+        }
+
+        #[test]
+        fn ranges_are_canonical(ranges in ranges_strategy()) {
+            let set: IntervalSet<u8> = ranges.iter().map(|&(s, e)| s..=e).collect();
+            let stored: Vec<_> = set.ranges().collect();
+
+            for pair in stored.windows(2) {
+                prop_assert!(*pair[0].end() as u16 + 1 < *pair[1].start() as u16);
+            }
+        }
+
+        #[test]
+        fn complement_matches_bitmap(ranges in ranges_strategy(), low in 0..=BOUND, high in 0..=BOUND) {
+            let set: IntervalSet<u8> = ranges.iter().map(|&(s, e)| s..=e).collect();
+            let map = bitmap(&ranges);
+            let complement = set.complement(low..=high);
+
+            for v in 0..=BOUND {
+                let expected = (low..=high).contains(&v) && !map[v as usize];
+                prop_assert_eq!(complement.contains(v), expected);
+            }
+
+            let gap = (low..=high).find(|&v| !map[v as usize]);
+            prop_assert_eq!(set.first_gap(low..=high), gap);
+            prop_assert_eq!(
+                set.uncovered_count(low..=high),
+                (low..=high).filter(|&v| !map[v as usize]).count() as u128
+            );
+        }
+    }
+}
@@ -3,6 +3,90 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
+pub mod assembunny;
+pub mod generate;
+pub mod hashing;
+pub mod interval;
+pub mod math;
+pub mod ocr;
+pub mod report;
+pub mod runner;
+pub mod search;
+pub mod solution;
+
+/// Implements [`solution::Solution`] for `$name` on top of a day's `#[aoc]`
+/// functions and registers it as the module's `ENTRY`.
+///
+/// The functions may return their value directly or as a `Result`. Leaving
+/// out `part2` makes a single part day.
+macro_rules! solution {
+    (
+        $name:ident {
+            day: $day:literal,
+            title: $title:literal,
+            input: $input:ty = $parse:expr,
+            part1: $output1:ty = $part1:expr,
+            part2: $output2:ty = $part2:expr $(,)?
+        }
+    ) => {
+        solution!(
+            @impl $name, $day, $title, 2,
+            $input = $parse,
+            $output1 = $part1,
+            $output2 = $part2
+        );
+    };
+    (
+        $name:ident {
+            day: $day:literal,
+            title: $title:literal,
+            input: $input:ty = $parse:expr,
+            part1: $output1:ty = $part1:expr $(,)?
+        }
+    ) => {
+        solution!(
+            @impl $name, $day, $title, 1,
+            $input = $parse,
+            $output1 = $part1,
+            ::std::convert::Infallible = |_: &$input| {
+                Err::<::std::convert::Infallible, _>(format!("Day {} has no second part", $day))
+            }
+        );
+    };
+    (
+        @impl $name:ident, $day:literal, $title:literal, $parts:literal,
+        $input:ty = $parse:expr,
+        $output1:ty = $part1:expr,
+        $output2:ty = $part2:expr
+    ) => {
+        struct $name;
+
+        impl $crate::solution::Solution for $name {
+            const DAY: u32 = $day;
+            const TITLE: &str = $title;
+            const PARTS: u32 = $parts;
+
+            type Input = $input;
+            type Output1 = $output1;
+            type Output2 = $output2;
+
+            fn parse(input: &str) -> Result<$input, Box<dyn ::std::error::Error>> {
+                $crate::solution::IntoResult::into_result(($parse)(input))
+            }
+
+            fn part1(input: &$input) -> Result<$output1, Box<dyn ::std::error::Error>> {
+                $crate::solution::IntoResult::into_result(($part1)(input))
+            }
+
+            fn part2(input: &$input) -> Result<$output2, Box<dyn ::std::error::Error>> {
+                $crate::solution::IntoResult::into_result(($part2)(input))
+            }
+        }
+
+        pub(crate) const ENTRY: $crate::solution::Entry = $crate::solution::Entry::new::<$name>();
+    };
+}
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -10,5 +94,19 @@ pub mod day04;
 pub mod day05;
 pub mod day06;
 pub mod day07;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
 
 aoc_lib! { year = 2016 }
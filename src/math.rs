@@ -0,0 +1,356 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+/// Primitive integers the helpers in this module work on.
+///
+/// Every helper returns `None` instead of overflowing.
+pub trait Integer:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+/// Integers that can hold the negative Bézout coefficients.
+pub trait Signed: Integer {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+fn checked_abs<T: Integer>(x: T) -> Option<T> {
+    if x < T::ZERO {
+        x.checked_neg()
+    } else {
+        Some(x)
+    }
+}
+
+/// `x` reduced into `0..m`, for `m > 0`.
+pub fn rem_euclid<T: Integer>(x: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+
+    let r = x % m;
+    if r < T::ZERO {
+        r.checked_add(m)
+    } else {
+        Some(r)
+    }
+}
+
+/// Non-negative greatest common divisor, with `gcd(0, 0) == 0`.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a.checked_rem(b)?);
+    }
+
+    checked_abs(a)
+}
+
+/// Non-negative least common multiple, with `lcm(x, 0) == 0`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    let g = gcd(a, b)?;
+    checked_abs(a.checked_div(g)?.checked_mul(b)?)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r.checked_div(r)?;
+        (old_r, r) = (r, old_r.checked_sub(q.checked_mul(r)?)?);
+        (old_x, x) = (x, old_x.checked_sub(q.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(q.checked_mul(y)?)?);
+    }
+
+    if old_r < T::ZERO {
+        Some((
+            old_r.checked_neg()?,
+            old_x.checked_neg()?,
+            old_y.checked_neg()?,
+        ))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// `x` such that `a * x ≡ 1 (mod m)`, in `0..m`.
+pub fn mod_inverse<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(rem_euclid(a, m)?, m)?;
+    if g != T::ONE {
+        return None;
+    }
+
+    rem_euclid(x, m)
+}
+
+// (a + b) % m for a, b in 0..m, without ever exceeding m.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `(a * b) % m` for `m > 0`, even when `a * b` itself would overflow.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> Option<T> {
+    let (a, mut b) = (rem_euclid(a, m)?, rem_euclid(b, m)?);
+    if let Some(product) = a.checked_mul(b) {
+        return Some(product % m);
+    }
+
+    let two = T::ONE + T::ONE;
+    let (mut result, mut doubled) = (T::ZERO, a);
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, doubled, m);
+        }
+        doubled = add_mod(doubled, doubled, m);
+        b = b / two;
+    }
+
+    Some(result)
+}
+
+/// `base.pow(exp) % m` for `exp >= 0` and `m > 0`.
+pub fn mod_pow<T: Integer>(base: T, exp: T, m: T) -> Option<T> {
+    if exp < T::ZERO {
+        return None;
+    }
+
+    let two = T::ONE + T::ONE;
+    let mut result = rem_euclid(T::ONE, m)?;
+    let mut base = rem_euclid(base, m)?;
+    let mut exp = exp;
+
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = mul_mod(result, base, m)?;
+        }
+        base = mul_mod(base, base, m)?;
+        exp = exp / two;
+    }
+
+    Some(result)
+}
+
+/// Solves the system `t ≡ residue (mod modulus)` for every pair in `congruences`.
+///
+/// Moduli do not have to be pairwise coprime. Returns the smallest non-negative
+/// solution together with the combined modulus, or `None` if the system has no
+/// solution or the combined modulus overflows.
+pub fn crt<T: Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut residue = T::ZERO;
+    let mut modulus = T::ONE;
+
+    for &(r, m) in congruences {
+        let r = rem_euclid(r, m)?;
+        let (g, p, _) = extended_gcd(modulus, m)?;
+        let diff = r - residue;
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        // modulus * k ≡ diff (mod m), with k in 0..step.
+        let step = m / g;
+        let k = mul_mod(diff / g, p, step)?;
+        residue = residue.checked_add(modulus.checked_mul(k)?)?;
+        modulus = modulus.checked_mul(step)?;
+    }
+
+    Some((residue, modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn naive_gcd(a: i64, b: i64) -> i64 {
+        let (a, b) = (a.abs(), b.abs());
+        (1..=a.max(b))
+            .rev()
+            .find(|d| a % d == 0 && b % d == 0)
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn extended_gcd_bezout() {
+        for a in -30..30_i64 {
+            for b in -30..30 {
+                let (g, x, y) = extended_gcd(a, b).unwrap();
+                assert_eq!(a * x + b * y, g);
+                assert_eq!(g, naive_gcd(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        assert_eq!(gcd(i32::MIN, 0), None);
+        assert_eq!(gcd(i32::MIN, -1), None);
+        assert_eq!(lcm(i32::MIN, -1), None);
+        assert_eq!(extended_gcd(i64::MIN, -1), None);
+        assert_eq!(lcm(u8::MAX, u8::MAX - 1), None);
+        assert_eq!(lcm(-4_i8, 6), Some(12));
+        assert_eq!(crt(&[(0_i8, 101), (0, 103)]), None);
+        assert_eq!(mod_pow(2_u8, 7, 0), None);
+    }
+
+    #[test]
+    fn mul_mod_near_limits() {
+        let m = u64::MAX - 58;
+        assert_eq!(
+            mul_mod(u64::MAX - 1, u64::MAX - 2, m),
+            Some(((u64::MAX - 1) as u128 * (u64::MAX - 2) as u128 % m as u128) as u64)
+        );
+        assert_eq!(mod_pow(3_u64, m - 1, m), Some(1));
+    }
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn crt_not_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn crt_matches_search() {
+        for m1 in 1..12_i32 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let expected = (0..m1 * m2).find(|t| t % m1 == r1 && t % m2 == r2);
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]).map(|s| s.0), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_lcm_match_naive(a in -500_i64..500, b in -500_i64..500) {
+            let g = naive_gcd(a, b);
+            prop_assert_eq!(gcd(a, b), Some(g));
+
+            let expected_lcm = if a == 0 || b == 0 {
+                0
+            } else {
+                (1..).map(|k| k * a.abs()).find(|x| x % b == 0).unwrap()
+            };
+            prop_assert_eq!(lcm(a, b), Some(expected_lcm));
+        }
+
+        #[test]
+        fn unsigned_gcd(a: u32, b: u32) {
+            let g = gcd(a, b).unwrap();
+            if g != 0 {
+                prop_assert_eq!(a % g, 0);
+                prop_assert_eq!(b % g, 0);
+                prop_assert_eq!(gcd(a / g, b / g), Some(1));
+            }
+        }
+
+        #[test]
+        fn mod_inverse_matches_search(a in -200_i32..200, m in 1_i32..200) {
+            let expected = (0..m).find(|x| (a.rem_euclid(m) * x) % m == 1 % m);
+            prop_assert_eq!(mod_inverse(a, m), expected);
+        }
+
+        #[test]
+        fn mod_pow_matches_naive(base: u64, exp in 0_u64..300, m in 1_u64..) {
+            let mut expected = 1 % m as u128;
+            for _ in 0..exp {
+                expected = expected * (base % m) as u128 % m as u128;
+            }
+            prop_assert_eq!(mod_pow(base, exp, m), Some(expected as u64));
+        }
+
+        #[test]
+        fn crt_matches_search_three(
+            moduli in prop::array::uniform3(1_i64..10),
+            residues in prop::array::uniform3(-20_i64..20),
+        ) {
+            let system: Vec<_> = residues.into_iter().zip(moduli).collect();
+            let period: i64 = moduli.iter().product();
+            let expected = (0..period).find(|t| system.iter().all(|&(r, m)| (t - r).rem_euclid(m) == 0));
+
+            let solved = crt(&system);
+            prop_assert_eq!(solved.map(|s| s.0), expected);
+            if let Some((_, modulus)) = solved {
+                let expected_modulus = moduli.iter().fold(1, |acc, &m| lcm(acc, m).unwrap());
+                prop_assert_eq!(modulus, expected_modulus);
+            }
+        }
+    }
+}
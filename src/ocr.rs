@@ -0,0 +1,308 @@
+use std::{error, fmt};
+
+use fnv::FnvHashMap;
+use ndarray::ArrayView2;
+
+/// The 4x6 capitals used by most AoC screens, one per 5 column cell.
+const STANDARD: &[(char, &str)] = &[
+    ('A', ".##. #..# #..# #### #..# #..#"),
+    ('B', "###. #..# ###. #..# #..# ###."),
+    ('C', ".##. #..# #... #... #..# .##."),
+    ('E', "#### #... ###. #... #... ####"),
+    ('F', "#### #... ###. #... #... #..."),
+    ('G', ".##. #..# #... #.## #..# .###"),
+    ('H', "#..# #..# #### #..# #..# #..#"),
+    ('I', ".### ..#. ..#. ..#. ..#. .###"),
+    ('J', "..## ...# ...# ...# #..# .##."),
+    ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+    ('L', "#... #... #... #... #... ####"),
+    ('O', ".##. #..# #..# #..# #..# .##."),
+    ('P', "###. #..# #..# ###. #... #..."),
+    ('R', "###. #..# #..# ###. #.#. #..#"),
+    ('S', ".### #... #... .##. ...# ###."),
+    ('U', "#..# #..# #..# #..# #..# .##."),
+    ('Z', "#### ...# ..#. .#.. #... ####"),
+];
+
+/// Capitals that fill the whole 5 column cell, as drawn by the 2016 screen.
+const WIDE: &[(char, &str)] = &[('Y', "#...# #...# .#.#. ..#.. ..#.. ..#..")];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// A glyph definition has the wrong size or an unexpected character.
+    BadGlyph { ch: char, reason: &'static str },
+    /// The grid is not as tall as the font.
+    WrongHeight { expected: usize, found: usize },
+    /// The cell starting at `column` matches no glyph. `index` counts cells
+    /// from the left and `art` is the cell as drawn.
+    UnknownGlyph {
+        index: usize,
+        column: usize,
+        art: String,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadGlyph { ch, reason } => write!(f, "Glyph {ch:?}: {reason}"),
+            Self::WrongHeight { expected, found } => {
+                write!(f, "Expected a grid {expected} rows tall, got {found}")
+            }
+            Self::UnknownGlyph { index, column, art } => {
+                write!(f, "Unknown glyph {index} at column {column}:\n{art}")
+            }
+        }
+    }
+}
+
+impl error::Error for OcrError {}
+
+/// A table of fixed size glyphs laid out left to right in equal cells.
+///
+/// Each cell is `width + spacing` columns wide and the spacing columns must be
+/// blank. Glyphs narrower than `width` are padded with blank columns on the
+/// right.
+#[derive(Debug, Clone)]
+pub struct Font {
+    width: usize,
+    height: usize,
+    spacing: usize,
+    glyphs: FnvHashMap<Vec<bool>, char>,
+}
+
+impl Font {
+    pub fn new(width: usize, height: usize, spacing: usize) -> Self {
+        Self {
+            width,
+            height,
+            spacing,
+            glyphs: FnvHashMap::default(),
+        }
+    }
+
+    /// The 4x6 capitals with a blank column between letters.
+    pub fn standard() -> Self {
+        Self::from_table(4, 6, 1, STANDARD)
+    }
+
+    /// The 5x6 capitals, which may run right up to the next letter.
+    pub fn wide() -> Self {
+        Self::from_table(5, 6, 0, &[STANDARD, WIDE].concat())
+    }
+
+    fn from_table(width: usize, height: usize, spacing: usize, table: &[(char, &str)]) -> Self {
+        let mut font = Self::new(width, height, spacing);
+        for &(ch, art) in table {
+            font.insert(ch, art).expect("Built-in glyphs are valid");
+        }
+        font
+    }
+
+    fn pitch(&self) -> usize {
+        self.width + self.spacing
+    }
+
+    /// Adds a glyph drawn with `#` and `.`, rows separated by whitespace.
+    pub fn insert(&mut self, ch: char, art: &str) -> Result<(), OcrError> {
+        let bad = |reason| OcrError::BadGlyph { ch, reason };
+
+        let rows: Vec<&str> = art.split_whitespace().collect();
+        if rows.len() != self.height {
+            return Err(bad("wrong number of rows"));
+        }
+
+        let mut key = vec![false; self.height * self.pitch()];
+        for (r, row) in rows.iter().enumerate() {
+            if row.len() > self.width {
+                return Err(bad("row wider than the font"));
+            }
+
+            for (c, pixel) in row.bytes().enumerate() {
+                key[r * self.pitch() + c] = match pixel {
+                    b'#' => true,
+                    b'.' => false,
+                    _ => return Err(bad("expected '#' or '.'")),
+                };
+            }
+        }
+
+        if key.iter().all(|&lit| !lit) {
+            return Err(bad("blank glyph"));
+        }
+
+        self.glyphs.insert(key, ch);
+        Ok(())
+    }
+
+    /// Reads every cell of `grid` as a glyph. Blank cells become spaces and
+    /// trailing ones are dropped; a final cell may lack its spacing columns.
+    pub fn read(&self, grid: ArrayView2<bool>) -> Result<String, OcrError> {
+        let (height, width) = grid.dim();
+        if height != self.height {
+            return Err(OcrError::WrongHeight {
+                expected: self.height,
+                found: height,
+            });
+        }
+
+        let pitch = self.pitch();
+        let mut out = String::new();
+
+        for (index, column) in (0..width).step_by(pitch).enumerate() {
+            let key: Vec<bool> = (0..height)
+                .flat_map(|r| (column..column + pitch).map(move |c| (r, c)))
+                .map(|(r, c)| c < width && grid[(r, c)])
+                .collect();
+
+            if key.iter().all(|&lit| !lit) {
+                out.push(' ');
+            } else if let Some(&ch) = self.glyphs.get(&key) {
+                out.push(ch);
+            } else {
+                let art = key
+                    .chunks(pitch)
+                    .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                return Err(OcrError::UnknownGlyph { index, column, art });
+            }
+        }
+
+        out.truncate(out.trim_end().len());
+        Ok(out)
+    }
+}
+
+/// Reads `grid` with the standard font, falling back to the wide one.
+///
+/// The error from the standard font is returned if neither can read it.
+pub fn read(grid: ArrayView2<bool>) -> Result<String, OcrError> {
+    Font::standard()
+        .read(grid)
+        .or_else(|e| Font::wide().read(grid).map_err(|_| e))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::Array2;
+
+    use super::*;
+
+    fn grid(art: &str) -> Array2<bool> {
+        let rows: Vec<&str> = art.split_whitespace().collect();
+        let width = rows[0].len();
+        let pixels = rows.iter().flat_map(|row| row.bytes().map(|b| b == b'#'));
+        Array2::from_shape_vec((rows.len(), width), pixels.collect()).unwrap()
+    }
+
+    #[test]
+    fn standard_font() {
+        let screen = grid(
+            "
+            #..#.####.#....#.....##.
+            #..#.#....#....#....#..#
+            ####.###..#....#....#..#
+            #..#.#....#....#....#..#
+            #..#.#....#....#....#..#
+            #..#.####.####.####..##.
+            ",
+        );
+
+        assert_eq!(Font::standard().read(screen.view()).unwrap(), "HELLO");
+        assert_eq!(read(screen.view()).unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn wide_font() {
+        let screen = grid(
+            "
+            #...##..#.
+            #...##.#..
+            .#.#.##...
+            ..#..#.#..
+            ..#..#.#..
+            ..#..#..#.
+            ",
+        );
+
+        assert_eq!(Font::wide().read(screen.view()).unwrap(), "YK");
+        assert_eq!(read(screen.view()).unwrap(), "YK");
+        assert!(matches!(
+            Font::standard().read(screen.view()),
+            Err(OcrError::UnknownGlyph { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn blank_cells() {
+        let screen = grid(
+            "
+            #.........####......
+            #.........#.........
+            #.........###.......
+            #.........#.........
+            #.........#.........
+            ####......#.........
+            ",
+        );
+
+        assert_eq!(read(screen.view()).unwrap(), "L F");
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let screen = grid(
+            "
+            ####.#...
+            #....#...
+            ###..#...
+            #....#...
+            #....#...
+            ####.#...
+            ",
+        );
+
+        let err = Font::standard().read(screen.view()).unwrap_err();
+        assert_eq!(
+            err,
+            OcrError::UnknownGlyph {
+                index: 1,
+                column: 5,
+                art: ["#...."; 6].join("\n"),
+            }
+        );
+    }
+
+    #[test]
+    fn wrong_height() {
+        assert_eq!(
+            read(Array2::from_elem((5, 10), false).view()),
+            Err(OcrError::WrongHeight {
+                expected: 6,
+                found: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn custom_font() {
+        let mut font = Font::new(3, 3, 0);
+        font.insert('+', ".#. ### .#.").unwrap();
+        font.insert('x', "#.# .#. #.#").unwrap();
+
+        let screen = grid(
+            "
+            .#.#.#
+            ###.#.
+            .#.#.#
+            ",
+        );
+        assert_eq!(font.read(screen.view()).unwrap(), "+x");
+
+        assert!(font.insert('?', "### ###").is_err());
+        assert!(font.insert('?', "#### #... #...").is_err());
+        assert!(font.insert('?', "... ... ...").is_err());
+        assert!(font.insert('?', "#o# ... ...").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use std::{io, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    runner::{self, Run, RunError},
+    solution::Answer,
+};
+
+/// The outcome of running one part, flattened for dashboards.
+///
+/// Successful runs carry the answer and both durations; failed ones carry the
+/// error, and the parse duration if the input parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<Answer>,
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    pub parse: Option<Duration>,
+    #[serde(rename = "solve_ns", serialize_with = "nanos")]
+    pub solve: Option<Duration>,
+    pub error: Option<String>,
+}
+
+fn nanos<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration.map(|d| d.as_nanos()).serialize(serializer)
+}
+
+impl Record {
+    pub fn new(day: u32, part: u32, result: Result<Run, RunError>) -> Self {
+        match result {
+            Ok(run) => Self {
+                day,
+                part,
+                answer: Some(run.answer),
+                parse: Some(run.parse),
+                solve: Some(run.solve),
+                error: None,
+            },
+            Err(e) => Self {
+                day,
+                part,
+                answer: None,
+                parse: match e {
+                    RunError::Solve { parse, .. } => Some(parse),
+                    _ => None,
+                },
+                solve: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Runs one part and records the outcome, failures included.
+    pub fn run(day: u32, part: u32, input: &str) -> Self {
+        Self::new(day, part, runner::run(day, part, input))
+    }
+}
+
+/// Writes the records as a pretty-printed JSON array.
+pub fn write_json<W: io::Write>(records: &[Record], writer: W) -> serde_json::Result<()> {
+    serde_json::to_writer_pretty(writer, records)
+}
+
+/// Writes one CSV row per record under a header. Since CSV is untyped, the
+/// `answer_type` column says whether the answer is an `integer` or a `string`.
+pub fn write_csv<W: io::Write>(records: &[Record], writer: W) -> csv::Result<()> {
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record([
+        "day",
+        "part",
+        "answer",
+        "answer_type",
+        "parse_ns",
+        "solve_ns",
+        "error",
+    ])?;
+
+    let nanos = |d: Option<Duration>| d.map_or(String::new(), |d| d.as_nanos().to_string());
+    for record in records {
+        let answer_type = match record.answer {
+            Some(Answer::Integer(_)) => "integer",
+            Some(Answer::Text(_)) => "string",
+            None => "",
+        };
+
+        csv.write_record([
+            record.day.to_string(),
+            record.part.to_string(),
+            record
+                .answer
+                .as_ref()
+                .map_or(String::new(), Answer::to_string),
+            answer_type.to_string(),
+            nanos(record.parse),
+            nanos(record.solve),
+            record.error.clone().unwrap_or_default(),
+        ])?;
+    }
+
+    csv.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: 1,
+                answer: Some(Answer::Integer(12)),
+                parse: Some(Duration::from_micros(3)),
+                solve: Some(Duration::from_nanos(450)),
+                error: None,
+            },
+            Record {
+                day: 2,
+                part: 2,
+                answer: Some(Answer::Text("5DB3".to_string())),
+                parse: Some(Duration::from_nanos(10)),
+                solve: Some(Duration::from_nanos(20)),
+                error: None,
+            },
+            Record::new(
+                20,
+                1,
+                Err(RunError::Parse("Line 1: bad, \"quoted\"".into())),
+            ),
+            Record::new(
+                24,
+                2,
+                Err(RunError::Solve {
+                    parse: Duration::from_nanos(70),
+                    source: "Cannot reach 1 from 0".into(),
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn json() {
+        let mut out = vec![];
+        write_json(&records(), &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                {
+                    "day": 1,
+                    "part": 1,
+                    "answer": 12,
+                    "parse_ns": 3000,
+                    "solve_ns": 450,
+                    "error": null,
+                },
+                {
+                    "day": 2,
+                    "part": 2,
+                    "answer": "5DB3",
+                    "parse_ns": 10,
+                    "solve_ns": 20,
+                    "error": null,
+                },
+                {
+                    "day": 20,
+                    "part": 1,
+                    "answer": null,
+                    "parse_ns": null,
+                    "solve_ns": null,
+                    "error": "Failed to parse input: Line 1: bad, \"quoted\"",
+                },
+                {
+                    "day": 24,
+                    "part": 2,
+                    "answer": null,
+                    "parse_ns": 70,
+                    "solve_ns": null,
+                    "error": "Failed to solve: Cannot reach 1 from 0",
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = vec![];
+        write_csv(&records(), &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,part,answer,answer_type,parse_ns,solve_ns,error
+1,1,12,integer,3000,450,
+2,2,5DB3,string,10,20,
+20,1,,,,,\"Failed to parse input: Line 1: bad, \"\"quoted\"\"\"
+24,2,,,70,,Failed to solve: Cannot reach 1 from 0
+"
+        );
+    }
+
+    #[test]
+    fn records_runs() {
+        let record = Record::run(1, 1, "R2, L3");
+        assert_eq!(record.answer, Some(Answer::Integer(5)));
+        assert!(record.parse.is_some() && record.solve.is_some());
+        assert_eq!(record.error, None);
+
+        let record = Record::run(8, 1, "");
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error.as_deref(), Some("No solver for day 8 part 1"));
+
+        let record = Record::run(1, 1, "X5");
+        assert_eq!(record.answer, None);
+        assert!(record.error.unwrap().contains("Panicked"));
+    }
+}
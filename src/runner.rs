@@ -0,0 +1,80 @@
+use std::{error::Error, fmt, time::Duration};
+
+use crate::solution::{self, Answer};
+
+#[derive(Debug)]
+pub enum RunError {
+    UnknownPuzzle {
+        day: u32,
+        part: u32,
+    },
+    Parse(Box<dyn Error>),
+    /// The input parsed in `parse` but the part failed.
+    Solve {
+        parse: Duration,
+        source: Box<dyn Error>,
+    },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPuzzle { day, part } => {
+                write!(f, "No solver for day {day} part {part}")
+            }
+            Self::Parse(e) => write!(f, "Failed to parse input: {e}"),
+            Self::Solve { source, .. } => write!(f, "Failed to solve: {source}"),
+        }
+    }
+}
+
+impl Error for RunError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownPuzzle { .. } => None,
+            Self::Parse(e) | Self::Solve { source: e, .. } => Some(e.as_ref()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Run {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Runs one part on `input` through the [`solution::REGISTRY`], which shares
+/// its code with `cargo aoc` and treats trailing newlines the same way.
+pub fn run(day: u32, part: u32, input: &str) -> Result<Run, RunError> {
+    solution::find(day)
+        .ok_or(RunError::UnknownPuzzle { day, part })?
+        .run(part, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_registered_parts() {
+        assert_eq!(run(1, 1, "R2, L3\n").unwrap().answer, Answer::Integer(5));
+        assert_eq!(
+            run(1, 2, "R8, R4, R4, R8").unwrap().answer,
+            Answer::Integer(4)
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert!(matches!(
+            run(8, 1, ""),
+            Err(RunError::UnknownPuzzle { day: 8, part: 1 })
+        ));
+        assert!(matches!(
+            run(25, 2, ""),
+            Err(RunError::UnknownPuzzle { .. })
+        ));
+        assert!(matches!(run(20, 1, "1-x"), Err(RunError::Parse(_))));
+    }
+}
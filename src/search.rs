@@ -0,0 +1,554 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque, hash_map::Entry},
+    hash::Hash,
+    ops::Add,
+};
+
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// Edge weights the weighted searches can add up and compare.
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+impl_cost!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Lower bound on the remaining cost from a state to the goal.
+///
+/// A* and IDA* only return optimal paths if the estimate never overshoots.
+/// Any `Fn(&S) -> C` closure is a heuristic.
+pub trait Heuristic<S> {
+    type Cost: Cost;
+
+    fn estimate(&self, state: &S) -> Self::Cost;
+}
+
+impl<S, C: Cost, F: Fn(&S) -> C> Heuristic<S> for F {
+    type Cost = C;
+
+    fn estimate(&self, state: &S) -> C {
+        self(state)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// States whose neighbours were generated.
+    pub expanded: usize,
+    /// Largest number of states waiting to be expanded at once. For the
+    /// depth-first searches this is the deepest path explored.
+    pub peak_frontier: usize,
+}
+
+impl Stats {
+    fn frontier(&mut self, len: usize) {
+        self.peak_frontier = self.peak_frontier.max(len);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Found<S, C> {
+    /// Every state from the start to the goal, both included.
+    pub path: Vec<S>,
+    pub cost: C,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Outcome<S, C> {
+    pub found: Option<Found<S, C>>,
+    pub stats: Stats,
+}
+
+struct Node<S, C> {
+    state: S,
+    parent: usize,
+    cost: C,
+}
+
+// Every state reached so far with the cheapest known way to get there. The
+// start is its own parent.
+struct Tree<S, C> {
+    nodes: Vec<Node<S, C>>,
+    index: FnvHashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Tree<S, C> {
+    fn new(start: S) -> Self {
+        let mut index = FnvHashMap::default();
+        index.insert(start.clone(), 0);
+
+        Self {
+            nodes: vec![Node {
+                state: start,
+                parent: 0,
+                cost: C::ZERO,
+            }],
+            index,
+        }
+    }
+
+    // Records `state` as reached from `parent` for `cost`. Returns its index
+    // if that is the first or a cheaper way to get there.
+    fn relax(&mut self, state: S, parent: usize, cost: C) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Vacant(e) => {
+                let idx = self.nodes.len();
+                self.nodes.push(Node {
+                    state: e.key().clone(),
+                    parent,
+                    cost,
+                });
+                e.insert(idx);
+                Some(idx)
+            }
+            Entry::Occupied(e) => {
+                let node = &mut self.nodes[*e.get()];
+                if cost < node.cost {
+                    node.parent = parent;
+                    node.cost = cost;
+                    Some(*e.get())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn found(&self, mut idx: usize) -> Found<S, C> {
+        let cost = self.nodes[idx].cost;
+        let mut path = vec![self.nodes[idx].state.clone()];
+        while self.nodes[idx].parent != idx {
+            idx = self.nodes[idx].parent;
+            path.push(self.nodes[idx].state.clone());
+        }
+
+        path.reverse();
+        Found { path, cost }
+    }
+}
+
+/// Fewest steps from `start` to a state satisfying `is_goal`.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut tree = Tree::new(start);
+    let mut queue = VecDeque::from([0]);
+    let mut stats = Stats::default();
+    stats.frontier(queue.len());
+
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&tree.nodes[idx].state) {
+            return Outcome {
+                found: Some(tree.found(idx)),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        let steps = tree.nodes[idx].cost + 1;
+        for next in neighbours(&tree.nodes[idx].state) {
+            queue.extend(tree.relax(next, idx, steps));
+        }
+        stats.frontier(queue.len());
+    }
+
+    Outcome { found: None, stats }
+}
+
+/// Every state at most `max_steps` away from `start`, with its distance.
+pub fn reachable_within<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    max_steps: usize,
+) -> FnvHashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = FnvHashMap::default();
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    distances.insert(start, 0);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if steps == max_steps {
+            continue;
+        }
+
+        for next in neighbours(&state) {
+            if let Entry::Vacant(e) = distances.entry(next) {
+                queue.push_back((e.key().clone(), steps + 1));
+                e.insert(steps + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Cheapest path from `start` to a state satisfying `is_goal`.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, &|_: &S| C::ZERO, is_goal)
+}
+
+/// Like [`dijkstra`], but expands states in order of cost so far plus the
+/// heuristic's estimate of the cost still to go.
+pub fn astar<S, C, I, H>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    heuristic: &H,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    H: Heuristic<S, Cost = C>,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic.estimate(&start), C::ZERO, 0))]);
+    let mut tree = Tree::new(start);
+    let mut stats = Stats::default();
+    stats.frontier(heap.len());
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        // Stale entry, the state has been reached more cheaply since.
+        if cost > tree.nodes[idx].cost {
+            continue;
+        }
+
+        if is_goal(&tree.nodes[idx].state) {
+            return Outcome {
+                found: Some(tree.found(idx)),
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        for (next, step) in neighbours(&tree.nodes[idx].state) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic.estimate(&next);
+            if let Some(next_idx) = tree.relax(next, idx, next_cost) {
+                heap.push(Reverse((estimate, next_cost, next_idx)));
+            }
+        }
+        stats.frontier(heap.len());
+    }
+
+    Outcome { found: None, stats }
+}
+
+struct Bounded<'a, S, N, H, G> {
+    neighbours: N,
+    heuristic: &'a H,
+    is_goal: G,
+    path: Vec<S>,
+    on_path: FnvHashSet<S>,
+    stats: Stats,
+}
+
+impl<S, C, I, N, H, G> Bounded<'_, S, N, H, G>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    N: FnMut(&S) -> I,
+    H: Heuristic<S, Cost = C>,
+    G: FnMut(&S) -> bool,
+{
+    // Depth-first search from the top of `path`, pruning states whose cost
+    // plus estimate exceeds `bound`. On failure returns the smallest pruned
+    // value, or `None` if nothing was pruned and searching again is useless.
+    fn search(&mut self, cost: C, bound: C) -> Result<C, Option<C>> {
+        let state = self.path.last().expect("Path starts at the start");
+        let estimate = cost + self.heuristic.estimate(state);
+        if estimate > bound {
+            return Err(Some(estimate));
+        }
+
+        if (self.is_goal)(state) {
+            return Ok(cost);
+        }
+
+        self.stats.expanded += 1;
+        self.stats.frontier(self.path.len());
+        let mut next_bound: Option<C> = None;
+        for (next, step) in (self.neighbours)(state) {
+            if !self.on_path.insert(next.clone()) {
+                continue;
+            }
+
+            self.path.push(next);
+
+            match self.search(cost + step, bound) {
+                Ok(total) => return Ok(total),
+                Err(pruned) => {
+                    next_bound = match (next_bound, pruned) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                }
+            }
+
+            let next = self.path.pop().expect("Pushed above");
+            self.on_path.remove(&next);
+        }
+
+        Err(next_bound)
+    }
+}
+
+fn bounded<'a, S, C, I, N, H, G>(
+    start: S,
+    neighbours: N,
+    heuristic: &'a H,
+    is_goal: G,
+) -> Bounded<'a, S, N, H, G>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    H: Heuristic<S, Cost = C>,
+{
+    let mut on_path = FnvHashSet::default();
+    on_path.insert(start.clone());
+
+    Bounded {
+        neighbours,
+        heuristic,
+        is_goal,
+        path: vec![start],
+        on_path,
+        stats: Stats::default(),
+    }
+}
+
+/// Iterative deepening A*: repeated depth-first searches with a growing bound
+/// on cost plus estimate. Only the current path is kept in memory, so states
+/// may be expanded many times.
+pub fn ida_star<S, C, I, H>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: &H,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Outcome<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+    H: Heuristic<S, Cost = C>,
+{
+    let mut bound = heuristic.estimate(&start);
+    let mut search = bounded(start, neighbours, heuristic, is_goal);
+
+    loop {
+        match search.search(C::ZERO, bound) {
+            Ok(cost) => {
+                return Outcome {
+                    found: Some(Found {
+                        path: search.path,
+                        cost,
+                    }),
+                    stats: search.stats,
+                };
+            }
+            Err(Some(next_bound)) => bound = next_bound,
+            Err(None) => {
+                return Outcome {
+                    found: None,
+                    stats: search.stats,
+                };
+            }
+        }
+    }
+}
+
+/// Depth-first search for any path of at most `max_depth` steps that does not
+/// visit a state twice. The path found is not necessarily the shortest.
+pub fn dfs_bounded<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+    max_depth: usize,
+) -> Outcome<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let unit_steps = |state: &S| neighbours(state).into_iter().map(|next| (next, 1));
+    let mut search = bounded(start, unit_steps, &|_: &S| 0, is_goal);
+
+    let found = search.search(0, max_depth).ok().map(|cost| Found {
+        path: search.path,
+        cost,
+    });
+
+    Outcome {
+        found,
+        stats: search.stats,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `#` are walls, digits are the cost of stepping onto a cell.
+    const GRID: &[&str] = &[
+        "11111111", //
+        "1###1##1", //
+        "1#991#11", //
+        "1#1#1#11", //
+        "1191111#", //
+        "######11",
+    ];
+
+    type Cell = (usize, usize);
+
+    fn weight((row, col): Cell) -> Option<u32> {
+        let c = *GRID.get(row)?.as_bytes().get(col)?;
+        (c != b'#').then(|| (c - b'0') as u32)
+    }
+
+    fn steps((row, col): Cell) -> Vec<Cell> {
+        [
+            row.checked_sub(1).map(|r| (r, col)),
+            Some((row + 1, col)),
+            col.checked_sub(1).map(|c| (row, c)),
+            Some((row, col + 1)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|&cell| weight(cell).is_some())
+        .collect()
+    }
+
+    fn weighted(cell: &Cell) -> Vec<(Cell, u32)> {
+        steps(*cell)
+            .into_iter()
+            .map(|next| (next, weight(next).unwrap()))
+            .collect()
+    }
+
+    fn manhattan(to: Cell) -> impl Fn(&Cell) -> u32 {
+        move |&(row, col)| (row.abs_diff(to.0) + col.abs_diff(to.1)) as u32
+    }
+
+    const GOAL: Cell = (5, 7);
+
+    fn path_cost(path: &[Cell]) -> u32 {
+        path[1..].iter().map(|&cell| weight(cell).unwrap()).sum()
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let outcome = bfs((0, 0), |&cell| steps(cell), |&cell| cell == GOAL);
+        let found = outcome.found.unwrap();
+
+        assert_eq!(found.cost, 12);
+        assert_eq!(found.path.len(), 13);
+        assert_eq!(found.path.first(), Some(&(0, 0)));
+        assert_eq!(found.path.last(), Some(&GOAL));
+        assert!(outcome.stats.expanded > 0);
+        assert!(outcome.stats.peak_frontier > 0);
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        let expected = {
+            let found = dijkstra((0, 0), weighted, |&cell| cell == GOAL)
+                .found
+                .unwrap();
+            assert_eq!(path_cost(&found.path), found.cost);
+            found.cost
+        };
+
+        let astar = astar((0, 0), weighted, &manhattan(GOAL), |&cell| cell == GOAL);
+        let ida = ida_star((0, 0), weighted, &manhattan(GOAL), |&cell| cell == GOAL);
+
+        for found in [astar.found.unwrap(), ida.found.unwrap()] {
+            assert_eq!(found.cost, expected);
+            assert_eq!(path_cost(&found.path), found.cost);
+        }
+    }
+
+    #[test]
+    fn heuristic_prunes_expansions() {
+        let plain = dijkstra((0, 0), weighted, |&cell| cell == GOAL);
+        let guided = astar((0, 0), weighted, &manhattan(GOAL), |&cell| cell == GOAL);
+        assert!(guided.stats.expanded <= plain.stats.expanded);
+    }
+
+    #[test]
+    fn dijkstra_avoids_expensive_cells() {
+        // Straight through the 9s costs more than going around them.
+        let found = dijkstra((2, 4), weighted, |&cell| cell == (4, 1))
+            .found
+            .unwrap();
+        assert_eq!(found.cost, 11);
+        assert!(!found.path.contains(&(4, 2)));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert_eq!(bfs((0, 0), |&cell| steps(cell), |_| false).found, None);
+        assert_eq!(dijkstra((0, 0), weighted, |_| false).found, None);
+        assert_eq!(
+            ida_star((0, 0), weighted, &|_: &Cell| 0, |&cell| cell == (9, 9)).found,
+            None
+        );
+    }
+
+    #[test]
+    fn dfs_respects_depth() {
+        let goal = |&cell: &Cell| cell == (0, 7);
+        assert_eq!(
+            dfs_bounded((0, 0), |&cell| steps(cell), goal, 6).found,
+            None
+        );
+
+        let outcome = dfs_bounded((0, 0), |&cell| steps(cell), goal, 7);
+        assert_eq!(outcome.found.unwrap().cost, 7);
+        assert!(outcome.stats.peak_frontier <= 8);
+    }
+
+    #[test]
+    fn reachable_matches_bfs() {
+        let reachable = reachable_within((0, 0), |&cell| steps(cell), usize::MAX);
+        for (&cell, &distance) in &reachable {
+            let found = bfs((0, 0), |&cell| steps(cell), |&c| c == cell)
+                .found
+                .unwrap();
+            assert_eq!(found.cost, distance);
+        }
+
+        let near = reachable_within((0, 0), |&cell| steps(cell), 3);
+        assert_eq!(near.len(), 7);
+        assert!(near.values().all(|&d| d <= 3));
+    }
+}
@@ -0,0 +1,387 @@
+use std::{
+    any::Any,
+    convert::Infallible,
+    error::Error,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::runner::{Run, RunError};
+
+/// One day's puzzle: how to parse the input and how to answer each part.
+///
+/// The `#[aoc]` entry points and this trait share the same functions, so
+/// `cargo aoc` and the [`REGISTRY`] always agree.
+pub trait Solution {
+    const DAY: u32;
+    const TITLE: &'static str;
+    /// Number of parts this day has; defaults to 2.
+    const PARTS: u32 = 2;
+
+    type Input;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1, Box<dyn Error>>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2, Box<dyn Error>>;
+}
+
+/// What an `#[aoc]` function returns: either the value itself or a `Result`
+/// with any error type.
+pub(crate) trait IntoResult<T> {
+    fn into_result(self) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T> IntoResult<T> for T {
+    fn into_result(self) -> Result<T, Box<dyn Error>> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Into<Box<dyn Error>>> IntoResult<T> for Result<T, E> {
+    fn into_result(self) -> Result<T, Box<dyn Error>> {
+        self.map_err(Into::into)
+    }
+}
+
+/// A puzzle answer, keeping numbers apart from text so reports can type them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+macro_rules! impl_integer_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    i128::try_from(n).map_or_else(|_| Self::Text(n.to_string()), Self::Integer)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Infallible> for Answer {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+/// A day's parsed input, ready to answer any of its parts.
+pub trait Parsed {
+    fn solve(&self, part: u32) -> Result<Answer, Box<dyn Error>>;
+}
+
+struct Input<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Input<S> {
+    fn solve(&self, part: u32) -> Result<Answer, Box<dyn Error>> {
+        match part {
+            1 => S::part1(&self.0).map(Into::into),
+            2 if S::PARTS >= 2 => S::part2(&self.0).map(Into::into),
+            _ => Err(format!("Day {} has no part {part}", S::DAY).into()),
+        }
+    }
+}
+
+type Parser = fn(&str) -> Result<Box<dyn Parsed>, Box<dyn Error>>;
+
+fn parse<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>, Box<dyn Error>> {
+    Ok(Box::new(Input::<S>(S::parse(input)?)))
+}
+
+/// A [`Solution`] with its types erased, so every day fits in one table.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub day: u32,
+    pub title: &'static str,
+    pub parts: u32,
+    parse: Parser,
+}
+
+impl Entry {
+    pub const fn new<S: Solution + 'static>() -> Self {
+        Self {
+            day: S::DAY,
+            title: S::TITLE,
+            parts: S::PARTS,
+            parse: parse::<S>,
+        }
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = u32> + use<> {
+        1..=self.parts
+    }
+
+    /// Parses `input` once so every part can be answered from it.
+    ///
+    /// Trailing newlines are dropped first, as `cargo aoc` does, and a panic is
+    /// returned as a parse error.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, RunError> {
+        self.try_parse(input).map_err(RunError::Parse)
+    }
+
+    fn try_parse(&self, input: &str) -> Result<Box<dyn Parsed>, Box<dyn Error>> {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            (self.parse)(input.trim_end_matches('\n'))
+        }))
+        .unwrap_or_else(|payload| Err(panicked(payload).into()))
+    }
+
+    fn check(&self, part: u32) -> Result<(), RunError> {
+        if self.parts().any(|p| p == part) {
+            Ok(())
+        } else {
+            Err(RunError::UnknownPuzzle {
+                day: self.day,
+                part,
+            })
+        }
+    }
+
+    // Answers `part` from an input that took `parse` to parse.
+    fn solve(&self, input: &dyn Parsed, part: u32, parse: Duration) -> Result<Run, RunError> {
+        self.check(part)?;
+
+        let start = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| input.solve(part)))
+            .unwrap_or_else(|payload| Err(panicked(payload).into()))
+            .map_err(|source| RunError::Solve { parse, source })?;
+        let solve = start.elapsed();
+
+        Ok(Run {
+            answer,
+            parse,
+            solve,
+        })
+    }
+
+    /// Parses `input` and answers `part`, timing both steps separately.
+    ///
+    /// A panic in either step is returned as that step's error.
+    pub fn run(&self, part: u32, input: &str) -> Result<Run, RunError> {
+        self.check(part)?;
+
+        let start = Instant::now();
+        let input = self.parse(input)?;
+        self.solve(input.as_ref(), part, start.elapsed())
+    }
+
+    /// Like [`Entry::run`] for each of `parts`, but parses `input` only once.
+    ///
+    /// Every part reports the same parse time, or the same parse error.
+    pub fn run_parts(&self, parts: &[u32], input: &str) -> Vec<Result<Run, RunError>> {
+        let start = Instant::now();
+        let parsed = self.try_parse(input);
+        let parse = start.elapsed();
+
+        parts
+            .iter()
+            .map(|&part| match &parsed {
+                Ok(input) => self.solve(input.as_ref(), part, parse),
+                Err(e) => {
+                    self.check(part)?;
+                    Err(RunError::Parse(e.to_string().into()))
+                }
+            })
+            .collect()
+    }
+}
+
+fn panicked(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("Panicked: {message}")
+}
+
+/// Every implemented day, in order.
+pub static REGISTRY: &[Entry] = &[
+    crate::day01::ENTRY,
+    crate::day02::ENTRY,
+    crate::day03::ENTRY,
+    crate::day04::ENTRY,
+    crate::day05::ENTRY,
+    crate::day06::ENTRY,
+    crate::day07::ENTRY,
+    crate::day12::ENTRY,
+    crate::day13::ENTRY,
+    crate::day14::ENTRY,
+    crate::day15::ENTRY,
+    crate::day16::ENTRY,
+    crate::day17::ENTRY,
+    crate::day18::ENTRY,
+    crate::day19::ENTRY,
+    crate::day20::ENTRY,
+    crate::day21::ENTRY,
+    crate::day22::ENTRY,
+    crate::day23::ENTRY,
+    crate::day24::ENTRY,
+    crate::day25::ENTRY,
+];
+
+pub fn find(day: u32) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_is_in_order() {
+        assert!(REGISTRY.windows(2).all(|pair| pair[0].day < pair[1].day));
+        assert!(REGISTRY.iter().all(|entry| entry.parts > 0));
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(find(1).unwrap().title, "No Time for a Taxicab");
+        assert_eq!(find(25).unwrap().title, "Clock Signal");
+        assert!(find(8).is_none());
+        assert!(REGISTRY.iter().all(|entry| !entry.title.is_empty()));
+    }
+
+    #[test]
+    fn runs_parts() {
+        let day01 = find(1).unwrap();
+        assert_eq!(
+            day01.run(1, "R5, L5, R5, R3\n").unwrap().answer,
+            Answer::Integer(12)
+        );
+        assert_eq!(
+            day01.run(2, "R8, R4, R4, R8").unwrap().answer,
+            Answer::Integer(4)
+        );
+        assert!(matches!(
+            day01.run(3, ""),
+            Err(RunError::UnknownPuzzle { day: 1, part: 3 })
+        ));
+        assert!(matches!(
+            find(25).unwrap().run(2, ""),
+            Err(RunError::UnknownPuzzle { day: 25, part: 2 })
+        ));
+    }
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const DAY: u32 = 0;
+        const TITLE: &str = "Panics";
+
+        type Input = ();
+        type Output1 = u8;
+        type Output2 = u8;
+
+        fn parse(_: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<u8, Box<dyn Error>> {
+            panic!("part 1")
+        }
+
+        fn part2(_: &()) -> Result<u8, Box<dyn Error>> {
+            panic!("{}", 2)
+        }
+    }
+
+    #[test]
+    fn catches_panics() {
+        let entry = Entry::new::<Panics>();
+        let solve_error = |part| match entry.run(part, "") {
+            Err(RunError::Solve { source, .. }) => source.to_string(),
+            other => panic!("Expected a solve error, got {other:?}"),
+        };
+        assert_eq!(solve_error(1), "Panicked: part 1");
+        assert_eq!(solve_error(2), "Panicked: 2");
+
+        let err = find(1).unwrap().run(1, "X5").unwrap_err();
+        assert!(matches!(err, RunError::Parse(_)));
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse input: Panicked: Cannot parse part of input \"X5\""
+        );
+    }
+
+    #[test]
+    fn runs_parts_on_one_parse() {
+        let day01 = find(1).unwrap();
+        let runs = day01.run_parts(&[1, 2, 3], "R8, R4, R4, R8");
+        let [Ok(part1), Ok(part2), Err(unknown)] = &runs[..] else {
+            panic!("Unexpected runs {runs:?}");
+        };
+        assert_eq!(part1.answer, Answer::Integer(8));
+        assert_eq!(part2.answer, Answer::Integer(4));
+        assert_eq!(part1.parse, part2.parse);
+        assert!(matches!(
+            unknown,
+            RunError::UnknownPuzzle { day: 1, part: 3 }
+        ));
+
+        let runs = day01.run_parts(&[1, 2], "X5");
+        assert!(
+            runs.iter()
+                .all(|run| matches!(run, Err(RunError::Parse(_))))
+        );
+    }
+
+    #[test]
+    fn parses_once() {
+        let parsed = find(1).unwrap().parse("R8, R4, R4, R8\n").unwrap();
+        assert_eq!(parsed.solve(1).unwrap(), Answer::Integer(8));
+        assert_eq!(parsed.solve(2).unwrap(), Answer::Integer(4));
+        assert!(parsed.solve(3).is_err());
+        assert!(
+            find(25)
+                .unwrap()
+                .parse("cpy 1 a")
+                .unwrap()
+                .solve(2)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn typed_answers() {
+        assert_eq!(Answer::from(42_usize), Answer::Integer(42));
+        assert_eq!(Answer::from(-7_i64), Answer::Integer(-7));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("5DB3"), Answer::Text("5DB3".to_string()));
+        assert_eq!(Answer::Integer(-7).to_string(), "-7");
+    }
+}
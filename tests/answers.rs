@@ -0,0 +1,155 @@
+use std::{collections::BTreeSet, fmt::Write, fs, path::Path};
+
+use aoc_2016::{runner, solution};
+use toml_edit::{DocumentMut, Item};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Case {
+    day: u32,
+    path: String,
+    // (part, expected answer)
+    answers: Vec<(u32, String)>,
+}
+
+fn expected_answer(item: &Item) -> Option<String> {
+    item.as_str()
+        .map(str::to_string)
+        .or_else(|| item.as_integer().map(|n| n.to_string()))
+}
+
+fn parse_answers(text: &str) -> Result<Vec<Case>, String> {
+    let doc: DocumentMut = text.parse().map_err(|e| format!("{e}"))?;
+    let Some(inputs) = doc.get("input") else {
+        return Ok(vec![]);
+    };
+    let inputs = inputs
+        .as_array_of_tables()
+        .ok_or("`input` must be an array of tables")?;
+
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, table)| {
+            let entry = i + 1;
+            let day = table
+                .get("day")
+                .and_then(Item::as_integer)
+                .and_then(|day| u32::try_from(day).ok())
+                .ok_or(format!("Input {entry} needs a day number"))?;
+            let path = table
+                .get("path")
+                .and_then(Item::as_str)
+                .ok_or(format!("Input {entry} needs a path"))?
+                .to_string();
+
+            let mut answers = vec![];
+            for (key, item) in table.iter() {
+                if key == "day" || key == "path" {
+                    continue;
+                }
+
+                let part = key
+                    .strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .ok_or(format!("Unexpected key {key:?} in input {entry}"))?;
+                if !solution::find(day).is_some_and(|entry| entry.parts().any(|p| p == part)) {
+                    return Err(format!("No solver for day {day} part {part}"));
+                }
+
+                let expected = expected_answer(item).ok_or(format!(
+                    "Answer for {key} in input {entry} must be a string or integer"
+                ))?;
+                answers.push((part, expected));
+            }
+
+            Ok(Case { day, path, answers })
+        })
+        .collect()
+}
+
+#[test]
+fn recorded_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let Ok(text) = fs::read_to_string(root.join("answers.toml")) else {
+        eprintln!("No answers.toml, nothing to check");
+        return;
+    };
+    let cases = parse_answers(&text).unwrap_or_else(|e| panic!("Invalid answers.toml: {e}"));
+
+    let mut failures = String::new();
+    let mut covered = BTreeSet::new();
+
+    for case in &cases {
+        let Ok(input) = fs::read_to_string(root.join(&case.path)) else {
+            eprintln!("Skipping day {}: {} not found", case.day, case.path);
+            continue;
+        };
+
+        for (part, expected) in &case.answers {
+            covered.insert((case.day, *part));
+
+            let location = format!("Day {} - Part {part} ({})", case.day, case.path);
+            match runner::run(case.day, *part, &input) {
+                Ok(run) if run.answer.to_string() == *expected => Ok(()),
+                Ok(run) => writeln!(
+                    failures,
+                    "{location}: expected {expected}, got {}",
+                    run.answer
+                ),
+                Err(e) => writeln!(failures, "{location}: {e}"),
+            }
+            .expect("Write to string failed");
+        }
+    }
+
+    let unchecked: Vec<_> = solution::REGISTRY
+        .iter()
+        .flat_map(|entry| entry.parts().map(|part| (entry.day, part)))
+        .filter(|key| !covered.contains(key))
+        .map(|(day, part)| format!("{day}.{part}"))
+        .collect();
+    if !unchecked.is_empty() {
+        eprintln!("No recorded answers for {}", unchecked.join(", "));
+    }
+
+    assert!(failures.is_empty(), "Answers changed:\n{failures}");
+}
+
+#[test]
+fn answer_format() {
+    let cases = parse_answers(
+        r#"
+        [[input]]
+        day = 2
+        path = "input/2016/day2.txt"
+        part1 = 1985
+        part2 = "5DB3"
+
+        [[input]]
+        day = 25
+        path = "other.txt"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        cases,
+        vec![
+            Case {
+                day: 2,
+                path: "input/2016/day2.txt".to_string(),
+                answers: vec![(1, "1985".to_string()), (2, "5DB3".to_string())],
+            },
+            Case {
+                day: 25,
+                path: "other.txt".to_string(),
+                answers: vec![],
+            },
+        ]
+    );
+
+    assert_eq!(parse_answers("").unwrap(), vec![]);
+    assert!(parse_answers("[[input]]\nday = 25\npath = \"x\"\npart2 = 1").is_err());
+    assert!(parse_answers("[[input]]\nday = 1\npath = \"x\"\nanswer = 1").is_err());
+    assert!(parse_answers("[[input]]\npath = \"x\"").is_err());
+}